@@ -1,9 +1,10 @@
+import argparse
 import importlib
 import os
 import pathlib
 import re
 
-from .utils.input import ensure_input_exists
+from .utils.input import ensure_input_exists, input_for_day
 
 
 def most_recent_day() -> int:
@@ -18,7 +19,34 @@ def run_day(n: int) -> None:
     importlib.import_module(f"aoc_2022.days.day_{n:02}").main()
 
 
+def run_day_in_rust(n: int) -> None:
+    """
+    Requires the extension module from ../rs; see README.md
+    """
+    try:
+        import aoc_2022_rs
+    except ImportError:
+        raise SystemExit(
+            "aoc_2022_rs isn't built; run `maturin develop --release` in rs/"
+        )
+    day = aoc_2022_rs.day(n)
+    data = input_for_day(n)
+    for part in range(1, day.parts + 1):
+        print(day.solve(part, data))
+
+
 def main():
-    most_recent_n = most_recent_day()
-    ensure_input_exists(most_recent_n)
-    run_day(most_recent_n)
+    parser = argparse.ArgumentParser(prog="aoc")
+    parser.add_argument("day", type=int, nargs="?", help="defaults to the latest one")
+    parser.add_argument(
+        "--rust", action="store_true", help="use the Rust solution instead"
+    )
+    args = parser.parse_args()
+    if args.rust:
+        if args.day is None:
+            parser.error("--rust needs a day")
+        run_day_in_rust(args.day)
+        return
+    n = most_recent_day() if args.day is None else args.day
+    ensure_input_exists(n)
+    run_day(n)
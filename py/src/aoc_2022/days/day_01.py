@@ -33,7 +33,9 @@ def part_1(data: str) -> int:
 
 
 def part_2(data: str) -> int:
-    return sum(elf.total for elf in ElfHeap(data).elves[:3])
+    # A heap's first three aren't necessarily its top three.
+    # (nsmallest gives the biggest totals, per Elf.__lt__)
+    return sum(elf.total for elf in heapq.nsmallest(3, ElfHeap(data).elves))
 
 
 def main():
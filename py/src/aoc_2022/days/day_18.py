@@ -29,12 +29,15 @@ def part_2(space: np.ndarray) -> int:
     return part_1(space)
 
 
+def parse(data: str) -> np.ndarray:
+    cubes = np.array([tuple(map(int, line.split(","))) for line in data.split()])
+    space = np.zeros(cubes.max(axis=0) + 1, dtype=bool)
+    space[tuple(cubes.T)] = True
+    return space
+
+
 def main():
-    data = np.array(
-        [tuple(map(int, line.split(","))) for line in input_for_day(18).split()]
-    )
-    space = np.zeros(data.max(axis=0) + 1, dtype=bool)
-    space[tuple(data.T)] = True
+    space = parse(input_for_day(18))
     print(part_1(space))
     print(part_2(space))
 
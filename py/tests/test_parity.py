@@ -0,0 +1,28 @@
+"""
+Checks the Python solutions against the Rust ones, which need building first;
+see README.md
+"""
+import pytest
+
+from aoc_2022.days import day_01, day_18
+
+aoc_2022_rs = pytest.importorskip("aoc_2022_rs")
+
+SEEDS = range(10)
+
+
+@pytest.mark.parametrize("seed", SEEDS)
+def test_day_01(seed: int):
+    data = aoc_2022_rs.generate_input(1, seed=seed, size=20)
+    rust = aoc_2022_rs.day(1)
+    assert day_01.part_1(data) == rust.part_1(data)
+    assert day_01.part_2(data) == rust.part_2(data)
+
+
+@pytest.mark.parametrize("seed", SEEDS)
+def test_day_18(seed: int):
+    data = aoc_2022_rs.generate_input(18, seed=seed, size=200)
+    rust = aoc_2022_rs.day(18)
+    space = day_18.parse(data)
+    assert day_18.part_1(space) == rust.part_1(data)
+    assert day_18.part_2(space) == rust.part_2(data)
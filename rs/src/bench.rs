@@ -0,0 +1,281 @@
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt,
+    io::{Read, Write},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{error::ParseError, runner::Day};
+
+/// How hard to try: up to `samples` runs of each part, but stopping early once `budget` has been
+/// spent on a part, as long as there's at least one sample.
+#[derive(Clone, Debug)]
+pub struct Options {
+    pub samples: usize,
+    pub budget: Duration,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            samples: 20,
+            budget: Duration::from_secs(5),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub samples: usize,
+    pub median: Duration,
+    /// 95th percentile, by nearest rank.
+    pub p95: Duration,
+    /// Sample standard deviation, or zero with fewer than two samples.
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Panics if there are no samples.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "no samples");
+        samples.sort();
+        let n = samples.len();
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        } else {
+            samples[n / 2]
+        };
+        let p95 = samples[(n * 95).div_ceil(100) - 1];
+        let secs: Vec<f64> = samples.iter().map(Duration::as_secs_f64).collect();
+        let mean = secs.iter().sum::<f64>() / n as f64;
+        let variance = if n > 1 {
+            secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+        } else {
+            0.0
+        };
+        Self {
+            samples: n,
+            median,
+            p95,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+/// Timings for one day's generator (`part` is `None`) or for one of its parts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Measurement {
+    pub day: u8,
+    /// Which alternate solution was timed, if it wasn't the day's default.
+    pub implementation: Option<&'static str>,
+    pub part: Option<u8>,
+    pub stats: Stats,
+}
+
+impl fmt::Display for Measurement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Day {}", self.day)?;
+        if let Some(name) = self.implementation {
+            write!(f, " ({})", name)?;
+        }
+        match self.part {
+            Some(part) => write!(f, " - Part {}", part)?,
+            None => write!(f, " - generator")?,
+        }
+        write!(
+            f,
+            ": median {:?}, p95 {:?}, stddev {:?} ({} samples)",
+            self.stats.median, self.stats.p95, self.stats.stddev, self.stats.samples
+        )
+    }
+}
+
+/// Time the generator and either the given part or all of them. The generator runs afresh for
+/// every sample, so its timings come from all the parts' runs put together.
+pub fn bench(
+    day: &Day,
+    input: &str,
+    part: Option<u8>,
+    options: &Options,
+) -> Result<Vec<Measurement>, ParseError> {
+    let mut generator = Vec::new();
+    let mut parts = Vec::new();
+    for part in part.map_or(1..=day.parts, |p| p..=p) {
+        let start = Instant::now();
+        let mut solve = Vec::new();
+        while solve.len() < options.samples.max(1)
+            && (solve.is_empty() || start.elapsed() < options.budget)
+        {
+            for run in day.run(input, Some(part))? {
+                generator.push(run.generator_time);
+                solve.push(run.solve_time);
+            }
+        }
+        if !solve.is_empty() {
+            parts.push((part, Stats::from_samples(solve)));
+        }
+    }
+    if generator.is_empty() {
+        return Ok(Vec::new());
+    }
+    let measurement = |part, stats| Measurement {
+        day: day.day,
+        implementation: day.alternate(),
+        part,
+        stats,
+    };
+    Ok([measurement(None, Stats::from_samples(generator))]
+        .into_iter()
+        .chain(parts.into_iter().map(|(p, s)| measurement(Some(p), s)))
+        .collect())
+}
+
+#[derive(Serialize, Deserialize)]
+struct Row {
+    day: u8,
+    #[serde(default)]
+    implementation: Option<String>,
+    part: Option<u8>,
+    samples: usize,
+    median_ns: u64,
+    p95_ns: u64,
+    stddev_ns: u64,
+}
+
+/// Earlier timings to compare against, saved as CSV with one row per [`Measurement`] and times in
+/// nanoseconds. Generator rows have an empty `part`, and default solutions an empty
+/// `implementation`.
+#[derive(Clone, Debug, Default)]
+pub struct Baseline {
+    stats: BTreeMap<(u8, Option<String>, Option<u8>), Stats>,
+}
+
+/// How a measurement compares to the baseline.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Change {
+    /// New median over old; above 1 means slower.
+    pub ratio: f64,
+    pub regressed: bool,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:+.1}% vs baseline", (self.ratio - 1.0) * 100.0)?;
+        if self.regressed {
+            write!(f, ", REGRESSION")?;
+        }
+        Ok(())
+    }
+}
+
+impl Baseline {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn Error>> {
+        let path = path.as_ref();
+        let file = std::fs::File::open(path)
+            .map_err(|e| format!("couldn't read {}: {}", path.display(), e))?;
+        Self::from_reader(file)
+    }
+
+    pub fn from_reader(reader: impl Read) -> Result<Self, Box<dyn Error>> {
+        let mut stats = BTreeMap::new();
+        for row in csv::Reader::from_reader(reader).deserialize() {
+            let row: Row = row?;
+            stats.insert(
+                (row.day, row.implementation, row.part),
+                Stats {
+                    samples: row.samples,
+                    median: Duration::from_nanos(row.median_ns),
+                    p95: Duration::from_nanos(row.p95_ns),
+                    stddev: Duration::from_nanos(row.stddev_ns),
+                },
+            );
+        }
+        Ok(Self { stats })
+    }
+
+    pub fn write(measurements: &[Measurement], out: impl Write) -> Result<(), Box<dyn Error>> {
+        let mut out = csv::Writer::from_writer(out);
+        for m in measurements {
+            out.serialize(Row {
+                day: m.day,
+                implementation: m.implementation.map(str::to_owned),
+                part: m.part,
+                samples: m.stats.samples,
+                median_ns: m.stats.median.as_nanos() as u64,
+                p95_ns: m.stats.p95.as_nanos() as u64,
+                stddev_ns: m.stats.stddev.as_nanos() as u64,
+            })?;
+        }
+        out.flush()?;
+        Ok(())
+    }
+
+    /// Compare medians, counting it as a regression if the new one is more than `threshold`
+    /// (e.g. 0.1 for 10%) slower. `None` if the baseline has nothing for this measurement.
+    pub fn compare(&self, m: &Measurement, threshold: f64) -> Option<Change> {
+        let key = (m.day, m.implementation.map(str::to_owned), m.part);
+        let old = self.stats.get(&key)?;
+        let ratio = m.stats.median.as_secs_f64() / old.median.as_secs_f64().max(f64::MIN_POSITIVE);
+        Some(Change {
+            ratio,
+            regressed: ratio > 1.0 + threshold,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner;
+
+    fn ms(ns: &[u64]) -> Vec<Duration> {
+        ns.iter().map(|&n| Duration::from_millis(n)).collect()
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::from_samples(ms(&[5, 1, 4, 2, 3]));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.p95, Duration::from_millis(5));
+        assert_eq!(stats.stddev.as_micros(), 1581);
+        let stats = Stats::from_samples(ms(&(1..=40).collect::<Vec<_>>()));
+        assert_eq!(stats.median, Duration::from_micros(20500));
+        assert_eq!(stats.p95, Duration::from_millis(38));
+        assert_eq!(Stats::from_samples(ms(&[7])).stddev, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_baseline() {
+        let options = Options {
+            samples: 3,
+            ..Options::default()
+        };
+        let input = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+        let measurements = bench(runner::day(1).unwrap(), input, None, &options).unwrap();
+        let parts: Vec<_> = measurements.iter().map(|m| m.part).collect();
+        assert_eq!(parts, vec![None, Some(1), Some(2)]);
+        assert_eq!(measurements[0].stats.samples, 6);
+        assert_eq!(measurements[1].stats.samples, 3);
+
+        let mut saved = Vec::new();
+        Baseline::write(&measurements, &mut saved).unwrap();
+        let baseline = Baseline::from_reader(saved.as_slice()).unwrap();
+        let mut slower = measurements[1].clone();
+        slower.stats.median = measurements[1].stats.median * 2;
+        assert!(baseline.compare(&slower, 0.1).unwrap().regressed);
+        assert!(!baseline.compare(&measurements[2], 0.1).unwrap().regressed);
+        slower.implementation = Some("other");
+        assert_eq!(baseline.compare(&slower, 0.1), None);
+        slower.day = 2;
+        slower.implementation = None;
+        assert_eq!(baseline.compare(&slower, 0.1), None);
+
+        let old = "day,part,samples,median_ns,p95_ns,stddev_ns\n1,1,3,1000,1000,0\n";
+        let baseline = Baseline::from_reader(old.as_bytes()).unwrap();
+        assert!(baseline.compare(&measurements[1], 0.1).is_some());
+    }
+}
@@ -1,36 +1,321 @@
-use std::collections::BinaryHeap;
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+    error::Error,
+    io::{self, BufRead},
+};
+
+use crate::{error::ParseError, runner};
+
+/// One elf's snacks, as listed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Elf {
+    /// Where the elf comes in the inventory, counting from 0.
+    pub index: usize,
+    pub items: Vec<usize>,
+}
+
+impl Elf {
+    pub fn total(&self) -> usize {
+        self.items.iter().sum()
+    }
+
+    pub fn stats(&self) -> ElfStats {
+        let total = self.total();
+        ElfStats {
+            index: self.index,
+            count: self.items.len(),
+            total,
+            max: self.items.iter().max().copied(),
+            mean: (!self.items.is_empty()).then(|| total as f64 / self.items.len() as f64),
+        }
+    }
+}
+
+/// A summary of one elf's snacks, which is all [`TopK`] needs to keep of an elf. `max` and `mean`
+/// are `None` for an elf with nothing.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ElfStats {
+    pub index: usize,
+    pub count: usize,
+    pub total: usize,
+    pub max: Option<usize>,
+    pub mean: Option<f64>,
+}
+
+/// Builds elves up a line at a time, for both parsing a whole string and streaming from a reader.
+/// A blank line ends an elf, and there's no such thing as an elf with no snacks, so two blank
+/// lines in a row are an error.
+#[derive(Default)]
+struct Tally {
+    line: usize,
+    index: usize,
+    items: Vec<usize>,
+}
+
+impl Tally {
+    /// The elf that `line` finishes, if it's the blank line after one.
+    fn line(&mut self, line: &str) -> Result<Option<Elf>, ParseError> {
+        self.line += 1;
+        if !line.is_empty() {
+            let item = line
+                .parse()
+                .map_err(|_| ParseError::new(self.line, 1, "calorie count").day(1))?;
+            self.items.push(item);
+            Ok(None)
+        } else if self.items.is_empty() {
+            Err(ParseError::new(self.line, 1, "calorie count").day(1))
+        } else {
+            Ok(self.finish())
+        }
+    }
+
+    /// The elf still being listed, if any.
+    fn finish(&mut self) -> Option<Elf> {
+        if self.items.is_empty() {
+            return None;
+        }
+        self.index += 1;
+        Some(Elf {
+            index: self.index - 1,
+            items: std::mem::take(&mut self.items),
+        })
+    }
+}
+
+/// Elves read one at a time, so an inventory never has to fit in memory all at once; from
+/// [`elves`].
+pub struct Elves<R> {
+    lines: io::Lines<R>,
+    tally: Tally,
+}
+
+/// Read elves from `reader` as they're needed. Errors are either [`io::Error`]s or
+/// [`ParseError`]s.
+pub fn elves<R: BufRead>(reader: R) -> Elves<R> {
+    Elves {
+        lines: reader.lines(),
+        tally: Tally::default(),
+    }
+}
+
+impl<R: BufRead> Iterator for Elves<R> {
+    type Item = Result<Elf, Box<dyn Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.lines.next() {
+                Some(Ok(line)) => match self.tally.line(&line) {
+                    Ok(Some(elf)) => return Some(Ok(elf)),
+                    Ok(None) => {}
+                    Err(e) => return Some(Err(e.into())),
+                },
+                Some(Err(e)) => return Some(Err(e.into())),
+                None => return self.tally.finish().map(Ok),
+            }
+        }
+    }
+}
+
+/// Orders elves best first: more calories, then whichever came first.
+struct Ranked(ElfStats);
+
+impl Ranked {
+    fn key(&self) -> (Reverse<usize>, usize) {
+        (Reverse(self.0.total), self.0.index)
+    }
+}
+
+impl PartialEq for Ranked {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Ranked {}
+
+impl PartialOrd for Ranked {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ranked {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+/// The `k` elves carrying the most calories, kept track of as they go by. When elves tie, the
+/// one that came first ranks higher, so the same inventory always gives the same elves.
+pub struct TopK {
+    k: usize,
+    // the worst of the best k so far is on top, ready to be pushed out
+    heap: BinaryHeap<Ranked>,
+}
+
+impl TopK {
+    pub fn new(k: usize) -> Self {
+        Self {
+            k,
+            heap: BinaryHeap::with_capacity(k + 1),
+        }
+    }
+
+    pub fn push(&mut self, elf: ElfStats) {
+        self.heap.push(Ranked(elf));
+        if self.heap.len() > self.k {
+            self.heap.pop();
+        }
+    }
+
+    /// Best first.
+    pub fn into_sorted(self) -> Vec<ElfStats> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|r| r.0)
+            .collect()
+    }
+
+    /// The top `k` of everything in `reader`, holding no more than one elf's snacks at a time.
+    pub fn from_reader(reader: impl BufRead, k: usize) -> Result<Vec<ElfStats>, Box<dyn Error>> {
+        let mut top = Self::new(k);
+        for elf in elves(reader) {
+            top.push(elf?.stats());
+        }
+        Ok(top.into_sorted())
+    }
+}
+
+/// Every elf with its snacks, in the order they were listed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Inventory {
+    pub elves: Vec<Elf>,
+}
+
+impl Inventory {
+    pub fn from_reader(reader: impl BufRead) -> Result<Self, Box<dyn Error>> {
+        let elves = elves(reader).collect::<Result<_, _>>()?;
+        Ok(Self { elves })
+    }
+
+    /// The `k` elves carrying the most, most first, with ties as in [`TopK`].
+    pub fn top(&self, k: usize) -> Vec<&Elf> {
+        let mut top = TopK::new(k);
+        self.stats().for_each(|s| top.push(s));
+        top.into_sorted()
+            .iter()
+            .map(|s| &self.elves[s.index])
+            .collect()
+    }
+
+    /// How many calories the top `k` elves have between them.
+    pub fn top_total(&self, k: usize) -> usize {
+        self.top(k).iter().map(|elf| elf.total()).sum()
+    }
+
+    pub fn stats(&self) -> impl Iterator<Item = ElfStats> + '_ {
+        self.elves.iter().map(Elf::stats)
+    }
+}
 
 #[aoc_generator(day1)]
-pub fn get_input(input: &str) -> BinaryHeap<usize> {
-    input
-        .split("\n\n")
-        .map(|s| s.lines().map(|l| l.parse::<usize>().unwrap()).sum())
-        .collect()
+pub fn get_input(input: &str) -> Result<Inventory, ParseError> {
+    let mut tally = Tally::default();
+    let mut elves = Vec::new();
+    for line in input.lines() {
+        elves.extend(tally.line(line)?);
+    }
+    elves.extend(tally.finish());
+    Ok(Inventory { elves })
 }
 
 #[aoc(day1, part1)]
-pub fn part_1(elves: &BinaryHeap<usize>) -> usize {
-    *elves.peek().unwrap()
+pub fn part_1(inventory: &Inventory) -> usize {
+    inventory.top_total(1)
 }
 
 #[aoc(day1, part2)]
-pub fn part_2(elves: &BinaryHeap<usize>) -> usize {
-    elves.iter().take(3).sum()
+pub fn part_2(inventory: &Inventory) -> usize {
+    inventory.top_total(3)
 }
 
+day!(Inventory, 1, usize, usize);
+
+pub static SOLUTIONS: [runner::Day; 1] = [runner::Day::of::<Inventory>()];
+
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
+    use crate::inputgen;
 
     const INPUT: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(&get_input(INPUT)), 24000);
+        assert_eq!(part_1(&get_input(INPUT).unwrap()), 24000);
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(&get_input(INPUT)), 45000);
+        assert_eq!(part_2(&get_input(INPUT).unwrap()), 45000);
+    }
+
+    #[test]
+    fn test_inventory() {
+        let inventory = get_input(INPUT).unwrap();
+        assert_eq!(inventory.elves.len(), 5);
+        let top: Vec<usize> = inventory.top(3).iter().map(|elf| elf.index).collect();
+        assert_eq!(top, vec![3, 2, 4]);
+        assert_eq!(inventory.top(1)[0].items, vec![7000, 8000, 9000]);
+        assert_eq!(inventory.top(10).len(), 5);
+        assert!(inventory.top(0).is_empty());
+        assert_eq!(
+            inventory.elves[2].stats(),
+            ElfStats {
+                index: 2,
+                count: 2,
+                total: 11000,
+                max: Some(6000),
+                mean: Some(5500.0),
+            }
+        );
+        assert_eq!(Elf::default().stats().mean, None);
+
+        // ties go to whichever elf came first
+        let tied = get_input("1\n\n2\n\n1\n1\n\n2").unwrap();
+        let top: Vec<usize> = tied.top(3).iter().map(|elf| elf.index).collect();
+        assert_eq!(top, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_streaming() {
+        let streamed = Inventory::from_reader(INPUT.as_bytes()).unwrap();
+        assert_eq!(streamed, get_input(INPUT).unwrap());
+        let top = TopK::from_reader(INPUT.as_bytes(), 2).unwrap();
+        let top: Vec<(usize, usize)> = top.iter().map(|s| (s.index, s.total)).collect();
+        assert_eq!(top, vec![(3, 24000), (2, 11000)]);
+
+        let err = elves("1\n\n\n2\n".as_bytes()).nth(1).unwrap().unwrap_err();
+        let err = err.downcast::<ParseError>().unwrap();
+        assert_eq!(*err, ParseError::new(3, 1, "calorie count").day(1));
+        assert_eq!(get_input("1\n\n\n2"), Err(*err));
+        assert!(get_input("").unwrap().elves.is_empty());
+    }
+
+    proptest! {
+        #[test]
+        fn test_top_three(input in inputgen::arbitrary(1, 1..=50)) {
+            let inventory = get_input(&input).unwrap();
+            let mut sorted: Vec<usize> = inventory.elves.iter().map(Elf::total).collect();
+            sorted.sort_by(|a, b| b.cmp(a));
+            prop_assert!(part_2(&inventory) >= part_1(&inventory));
+            prop_assert_eq!(part_2(&inventory), sorted.iter().take(3).sum::<usize>());
+            let top = TopK::from_reader(input.as_bytes(), 3).unwrap();
+            prop_assert_eq!(top.iter().map(|s| s.total).sum::<usize>(), part_2(&inventory));
+        }
     }
 }
@@ -1,155 +1,683 @@
-#[derive(Debug)]
-enum Outcome {
-    Win,
-    Draw,
+use crate::{error::ParseError, runner};
+
+/// How a round went for the player.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
     Lose,
+    Draw,
+    Win,
+}
+
+/// A game's moves and which beats which. Every pair of different moves has to have a winner, and
+/// every move has to beat something and lose to something, so any outcome can be played for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rules {
+    names: Vec<String>,
+    /// `beats[i][j]` if move `i` beats move `j`.
+    beats: Vec<Vec<bool>>,
 }
 
-impl TryFrom<char> for Outcome {
-    type Error = &'static str;
-    fn try_from(value: char) -> Result<Self, Self::Error> {
-        match value {
-            'X' => Ok(Self::Lose),
-            'Y' => Ok(Self::Draw),
-            'Z' => Ok(Self::Win),
-            _ => Err("parse error"),
+impl Rules {
+    /// Each move beats the half of the others that come just before it, going round in a circle,
+    /// so there has to be an odd number of them. Rock, paper, scissors in that order is the
+    /// usual game, and rock, Spock, paper, lizard, scissors is the one with the lizard.
+    pub fn cyclic<S: Into<String>>(names: impl IntoIterator<Item = S>) -> Result<Self, String> {
+        let names: Vec<String> = names.into_iter().map(Into::into).collect();
+        let n = names.len();
+        if n.is_multiple_of(2) {
+            return Err(format!(
+                "a cyclic game needs an odd number of moves, not {}",
+                n
+            ));
         }
+        let beats = (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| (1..=n / 2).contains(&((i + n - j) % n)))
+                    .collect()
+            })
+            .collect();
+        Self::checked(names, beats)
     }
-}
 
-impl Outcome {
-    fn score(&self) -> usize {
-        match self {
-            Self::Win => 6,
-            Self::Draw => 3,
-            Self::Lose => 0,
+    /// From every pair of moves spelled out as `(winner, loser)`.
+    pub fn from_table<S: Into<String>>(
+        names: impl IntoIterator<Item = S>,
+        wins: &[(&str, &str)],
+    ) -> Result<Self, String> {
+        let names: Vec<String> = names.into_iter().map(Into::into).collect();
+        let mut beats = vec![vec![false; names.len()]; names.len()];
+        let index = |name: &str| {
+            names
+                .iter()
+                .position(|n| n == name)
+                .ok_or_else(|| format!("no such move: {}", name))
+        };
+        for &(winner, loser) in wins {
+            let (w, l) = (index(winner)?, index(loser)?);
+            if w == l {
+                return Err(format!("{} can't beat itself", winner));
+            }
+            if beats[w][l] || beats[l][w] {
+                return Err(format!("{} and {} are listed twice", winner, loser));
+            }
+            beats[w][l] = true;
         }
+        Self::checked(names, beats)
+    }
+
+    pub fn rock_paper_scissors() -> Self {
+        Self::cyclic(["rock", "paper", "scissors"]).unwrap()
+    }
+
+    fn checked(names: Vec<String>, beats: Vec<Vec<bool>>) -> Result<Self, String> {
+        let n = names.len();
+        for i in 0..n {
+            if names[..i].contains(&names[i]) {
+                return Err(format!("{} is listed twice", names[i]));
+            }
+            for j in (0..n).filter(|&j| j != i) {
+                if !beats[i][j] && !beats[j][i] {
+                    return Err(format!(
+                        "nothing says whether {} beats {}",
+                        names[i], names[j]
+                    ));
+                }
+            }
+            if !beats[i].contains(&true) {
+                return Err(format!("{} doesn't beat anything", names[i]));
+            }
+            if !(0..n).any(|j| beats[j][i]) {
+                return Err(format!("nothing beats {}", names[i]));
+            }
+        }
+        Ok(Self { names, beats })
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn name(&self, i: usize) -> &str {
+        &self.names[i]
+    }
+
+    pub fn outcome(&self, me: usize, op: usize) -> Outcome {
+        if me == op {
+            Outcome::Draw
+        } else if self.beats[me][op] {
+            Outcome::Win
+        } else {
+            Outcome::Lose
+        }
+    }
+
+    /// The moves that come to `outcome` against `op`.
+    pub fn moves_for(&self, op: usize, outcome: Outcome) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len()).filter(move |&me| self.outcome(me, op) == outcome)
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-enum RPS {
-    Rock,
-    Paper,
-    Scissors,
+/// Points for a round: so many for the move played, plus so many for how it went.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Scoring {
+    /// By move, in the order of the [`Rules`].
+    pub shapes: Vec<usize>,
+    /// By outcome, in the order of [`Outcome`].
+    pub outcomes: [usize; 3],
 }
 
-impl RPS {
-    fn score(&self) -> usize {
-        match self {
-            RPS::Rock => 1,
-            RPS::Paper => 2,
-            RPS::Scissors => 3,
+impl Scoring {
+    /// 1 point for the first move, 2 for the second and so on, and 0, 3 or 6 for losing, drawing
+    /// or winning.
+    pub fn standard(moves: usize) -> Self {
+        Self {
+            shapes: (1..=moves).collect(),
+            outcomes: [0, 3, 6],
         }
     }
 
-    fn get_outcome(&self, outcome: Outcome) -> Self {
-        match outcome {
-            Outcome::Win => match self {
-                Self::Rock => Self::Paper,
-                Self::Paper => Self::Scissors,
-                Self::Scissors => Self::Rock,
-            },
-            Outcome::Draw => *self,
-            Outcome::Lose => match self {
-                Self::Rock => Self::Scissors,
-                Self::Paper => Self::Rock,
-                Self::Scissors => Self::Paper,
-            },
+    pub fn round(&self, me: usize, outcome: Outcome) -> usize {
+        self.shapes[me] + self.outcomes[outcome as usize]
+    }
+}
+
+/// Which letters in a strategy guide stand for what.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Encoding {
+    /// The opponent's moves, in the order of the [`Rules`].
+    pub opponent: Vec<char>,
+    /// The player's moves, when the second column is read as moves.
+    pub player: Vec<char>,
+    /// Lose, draw and win, when the second column is read as outcomes.
+    pub outcomes: [char; 3],
+}
+
+impl Encoding {
+    pub fn new(opponent: &str, player: &str, outcomes: [char; 3]) -> Self {
+        Self {
+            opponent: opponent.chars().collect(),
+            player: player.chars().collect(),
+            outcomes,
         }
     }
+}
 
-    fn against(&self, other: &RPS) -> Outcome {
-        match self {
-            Self::Rock => match other {
-                Self::Rock => Outcome::Draw,
-                Self::Paper => Outcome::Lose,
-                Self::Scissors => Outcome::Win,
-            },
-            Self::Paper => match other {
-                Self::Rock => Outcome::Win,
-                Self::Paper => Outcome::Draw,
-                Self::Scissors => Outcome::Lose,
-            },
-            Self::Scissors => match other {
-                Self::Rock => Outcome::Lose,
-                Self::Paper => Outcome::Win,
-                Self::Scissors => Outcome::Draw,
-            },
+/// How to read a guide's second column.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Strategy {
+    /// As the move to play, as in part 1.
+    Moves,
+    /// As how the round needs to end, as in part 2.
+    Outcomes,
+}
+
+/// The most moves [`Game::mappings`] will try every ordering of, at 8! = 40,320 of them.
+pub const MAX_MAPPED_MOVES: usize = 8;
+
+/// The most moves [`Game::equilibrium`] will try every set of, at 2^16 = 65,536 of them.
+pub const MAX_MIXED_MOVES: usize = 16;
+
+/// A game like rock, paper, scissors, put together from its rules, scoring and encoding so that
+/// variants don't need code of their own. The parts only go together through [`Game::new`],
+/// which checks that they agree on the number of moves.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Game {
+    rules: Rules,
+    scoring: Scoring,
+    encoding: Encoding,
+}
+
+/// `['A', 'B', 'C']` as `A, B or C`.
+fn one_of(letters: &[char]) -> String {
+    match letters {
+        [] => "nothing".to_owned(),
+        [letter] => letter.to_string(),
+        [rest @ .., last] => {
+            let rest: Vec<String> = rest.iter().map(char::to_string).collect();
+            format!("{} or {}", rest.join(", "), last)
         }
     }
 }
 
-impl TryFrom<char> for RPS {
-    type Error = &'static str;
-    fn try_from(value: char) -> Result<Self, Self::Error> {
-        match value {
-            'A' => Ok(RPS::Rock),
-            'B' => Ok(RPS::Paper),
-            'C' => Ok(RPS::Scissors),
-            'X' => Ok(RPS::Rock),
-            'Y' => Ok(RPS::Paper),
-            'Z' => Ok(RPS::Scissors),
-            _ => Err("invalid"),
+impl Game {
+    pub fn new(rules: Rules, scoring: Scoring, encoding: Encoding) -> Result<Self, String> {
+        let n = rules.len();
+        if scoring.shapes.len() != n {
+            return Err(format!(
+                "{} moves but {} shape scores",
+                n,
+                scoring.shapes.len()
+            ));
+        }
+        let columns = [
+            &encoding.opponent[..],
+            &encoding.player[..],
+            &encoding.outcomes[..],
+        ];
+        for (column, expected) in columns.into_iter().zip([n, n, 3]) {
+            if column.len() != expected {
+                return Err(format!("{} letters for {} things", column.len(), expected));
+            }
+            if (1..column.len()).any(|i| column[..i].contains(&column[i])) {
+                return Err(format!("a letter is used twice in {}", one_of(column)));
+            }
+        }
+        Ok(Self {
+            rules,
+            scoring,
+            encoding,
+        })
+    }
+
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    pub fn scoring(&self) -> &Scoring {
+        &self.scoring
+    }
+
+    pub fn encoding(&self) -> &Encoding {
+        &self.encoding
+    }
+
+    /// The game in the puzzle.
+    pub fn standard() -> Self {
+        Self::new(
+            Rules::rock_paper_scissors(),
+            Scoring::standard(3),
+            Encoding::new("ABC", "XYZ", ['X', 'Y', 'Z']),
+        )
+        .unwrap()
+    }
+
+    /// The move to make for `outcome` against `op`. When more than one move would do, as in
+    /// games with more than three moves, it's the one worth the most, then the first listed.
+    pub fn choose(&self, op: usize, outcome: Outcome) -> usize {
+        self.rules
+            .moves_for(op, outcome)
+            .max_by_key(|&me| (self.scoring.shapes[me], std::cmp::Reverse(me)))
+            .expect("rules let every outcome happen")
+    }
+
+    /// The player's score for one round, or `None` if `op` isn't a move or `second` isn't one
+    /// of the strategy's, such as a round read as moves in a game of more than three and then
+    /// played as outcomes.
+    pub fn round(&self, op: usize, second: usize, strategy: Strategy) -> Option<usize> {
+        let n = self.rules.len();
+        if op >= n {
+            return None;
+        }
+        let me = match strategy {
+            Strategy::Moves => Some(second).filter(|&me| me < n)?,
+            Strategy::Outcomes => {
+                let outcome = *[Outcome::Lose, Outcome::Draw, Outcome::Win].get(second)?;
+                self.choose(op, outcome)
+            }
+        };
+        Some(self.scoring.round(me, self.rules.outcome(me, op)))
+    }
+
+    /// A guide's rounds as the opponent's move and the index of the second column's letter, one
+    /// per line of an opponent's letter, a space and the second column's letter.
+    pub fn rounds(
+        &self,
+        guide: &str,
+        strategy: Strategy,
+    ) -> Result<Vec<(usize, usize)>, ParseError> {
+        let second = match strategy {
+            Strategy::Moves => &self.encoding.player[..],
+            Strategy::Outcomes => &self.encoding.outcomes[..],
+        };
+        let columns = [&self.encoding.opponent[..], &[' '], second];
+        let mut rounds = Vec::new();
+        for l in guide.lines() {
+            let mut cs = l.char_indices();
+            let mut found = [0; 3];
+            for (letters, found) in columns.iter().zip(found.iter_mut()) {
+                let expected = || match letters {
+                    [' '] => "a space".to_owned(),
+                    _ => one_of(letters),
+                };
+                match cs.next() {
+                    Some((_, c)) if letters.contains(&c) => {
+                        *found = letters.iter().position(|&l| l == c).unwrap();
+                    }
+                    Some((i, _)) => return Err(ParseError::at(guide, &l[i..], expected()).day(2)),
+                    None => return Err(ParseError::at(guide, &l[l.len()..], expected()).day(2)),
+                }
+            }
+            if let Some((i, _)) = cs.next() {
+                return Err(ParseError::at(guide, &l[i..], "end of line").day(2));
+            }
+            rounds.push((found[0], found[2]));
+        }
+        Ok(rounds)
+    }
+
+    /// The player's total score from rounds as [`Self::rounds`] gives them, or `None` if any
+    /// aren't rounds of this game, as for [`Self::round`].
+    pub fn score(&self, rounds: &[(usize, usize)], strategy: Strategy) -> Option<usize> {
+        rounds
+            .iter()
+            .map(|&(op, second)| self.round(op, second, strategy))
+            .sum()
+    }
+
+    /// The player's total score from following `guide`.
+    pub fn play(&self, guide: &str, strategy: Strategy) -> Result<usize, ParseError> {
+        let rounds = self.rounds(guide, strategy)?;
+        Ok(self
+            .score(&rounds, strategy)
+            .expect("rounds read the way they're played"))
+    }
+
+    /// The score from `rounds`, as [`Self::rounds`] reads them as moves, under every way of
+    /// reading the second column's letters as moves, best first. Part 1's reading is one of them,
+    /// so if it comes out well behind the best, or behind part 2's reading, the guide's author
+    /// probably meant something else. There are `n!` ways for `n` moves, so this gives up past
+    /// [`MAX_MAPPED_MOVES`].
+    pub fn mappings(&self, rounds: &[(usize, usize)]) -> Result<Vec<Mapping>, String> {
+        if self.rules.len() > MAX_MAPPED_MOVES {
+            return Err(format!(
+                "{} moves is too many to try every mapping of",
+                self.rules.len()
+            ));
         }
+        let mut mappings: Vec<Mapping> = permutations(self.rules.len())
+            .into_iter()
+            .map(|moves| {
+                let total = rounds
+                    .iter()
+                    .map(|&(op, letter)| self.round(op, *moves.get(letter)?, Strategy::Moves))
+                    .sum::<Option<usize>>()
+                    .ok_or_else(|| format!("rounds past this game's {} moves", moves.len()))?;
+                Ok(Mapping {
+                    moves,
+                    total,
+                    rounds: rounds.len(),
+                })
+            })
+            .collect::<Result<_, String>>()?;
+        // stable, so ties stay in the order of the moves
+        mappings.sort_by_key(|m| std::cmp::Reverse(m.total));
+        Ok(mappings)
+    }
+
+    /// The player's score for each move against each of the opponent's.
+    pub fn payoffs(&self) -> Vec<Vec<usize>> {
+        let n = self.rules.len();
+        (0..n)
+            .map(|me| {
+                (0..n)
+                    .map(|op| self.scoring.round(me, self.rules.outcome(me, op)))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// A mixed strategy that's a best response to itself: if both players score by
+    /// [`Self::payoffs`], neither gains by playing anything else while the other plays it. Found
+    /// by trying each set of moves to mix, smallest first, and solving for the mix that makes
+    /// the opponent indifferent between them. There are `2^n` sets for `n` moves, so this gives
+    /// up past [`MAX_MIXED_MOVES`]. Every game has such a mix, but for degenerate payoffs the
+    /// search can miss it to rounding, which is an error too.
+    pub fn equilibrium(&self) -> Result<Equilibrium, String> {
+        const EPSILON: f64 = 1e-9;
+        let a = self.payoffs();
+        let n = a.len();
+        if n > MAX_MIXED_MOVES {
+            return Err(format!("{} moves is too many to try every mix of", n));
+        }
+        let mut supports: Vec<u32> = (1..1 << n).collect();
+        supports.sort_by_key(|s| s.count_ones());
+        for support in supports {
+            let moves: Vec<usize> = (0..n).filter(|i| support & 1 << i != 0).collect();
+            let k = moves.len();
+            // each move in the support scores v against the mix, and the mix adds up to 1
+            let mut system: Vec<Vec<f64>> = moves
+                .iter()
+                .map(|&i| {
+                    let mut row: Vec<f64> = moves.iter().map(|&j| a[i][j] as f64).collect();
+                    row.extend([-1.0, 0.0]);
+                    row
+                })
+                .collect();
+            let mut total = vec![1.0; k];
+            total.extend([0.0, 1.0]);
+            system.push(total);
+            let Some(solution) = solve(system) else {
+                continue;
+            };
+            let (mix, value) = (&solution[..k], solution[k]);
+            if mix.iter().any(|&p| p < -EPSILON) {
+                continue;
+            }
+            let mut strategy = vec![0.0; n];
+            for (&i, &p) in moves.iter().zip(mix) {
+                strategy[i] = p.max(0.0);
+            }
+            let against = |i: usize| (0..n).map(|j| a[i][j] as f64 * strategy[j]).sum::<f64>();
+            if (0..n).all(|i| against(i) <= value + EPSILON) {
+                return Ok(Equilibrium { strategy, value });
+            }
+        }
+        Err("no mix of moves was found to be a best response to itself".to_owned())
     }
 }
 
-#[derive(Debug)]
-pub struct Match {
-    me: RPS,
-    op: RPS,
+/// One way of reading a guide's second column as moves, and what it scores.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mapping {
+    /// The move each letter stands for, in the order of [`Encoding::player`].
+    pub moves: Vec<usize>,
+    pub total: usize,
+    pub rounds: usize,
 }
 
-impl Match {
-    fn score(&self) -> usize {
-        self.me.score() + self.me.against(&self.op).score()
+impl Mapping {
+    /// The expected score of a round of the guide, if it has any.
+    pub fn mean(&self) -> Option<f64> {
+        (self.rounds > 0).then(|| self.total as f64 / self.rounds as f64)
     }
+}
 
-    fn from_str_1(value: &str) -> Self {
-        Self {
-            op: value.chars().next().unwrap().try_into().unwrap(),
-            me: value.chars().nth(2).unwrap().try_into().unwrap(),
+/// See [`Game::equilibrium`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Equilibrium {
+    /// How often to play each move, in the order of the [`Rules`].
+    pub strategy: Vec<f64>,
+    /// The expected score of a round when both players mix this way.
+    pub value: f64,
+}
+
+/// Every ordering of `0..n`, in lexicographic order.
+fn permutations(n: usize) -> Vec<Vec<usize>> {
+    if n == 0 {
+        return vec![vec![]];
+    }
+    let mut all = Vec::new();
+    for first in 0..n {
+        for rest in permutations(n - 1) {
+            let mut p = vec![first];
+            p.extend(rest.into_iter().map(|i| if i >= first { i + 1 } else { i }));
+            all.push(p);
         }
     }
+    all
+}
 
-    fn from_str_2(value: &str) -> Self {
-        let op = value.chars().next().unwrap().try_into().unwrap();
-        Self {
-            op,
-            me: op.get_outcome(Outcome::try_from(value.chars().nth(2).unwrap()).unwrap()),
+/// Gaussian elimination on an augmented matrix, or `None` if it's singular.
+fn solve(mut m: Vec<Vec<f64>>) -> Option<Vec<f64>> {
+    let n = m.len();
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&i, &j| m[i][col].abs().total_cmp(&m[j][col].abs()))?;
+        if m[pivot][col].abs() < 1e-12 {
+            return None;
+        }
+        m.swap(col, pivot);
+        for row in 0..n {
+            if row != col {
+                let factor = m[row][col] / m[col][col];
+                let pivot = m[col].clone();
+                for (x, p) in m[row][col..].iter_mut().zip(&pivot[col..]) {
+                    *x -= factor * p;
+                }
+            }
         }
     }
+    Some((0..n).map(|i| m[i][n] / m[i][i]).collect())
 }
 
+/// The standard game uses X, Y and Z for both moves and outcomes, so the same rounds do for
+/// either part.
 #[aoc_generator(day2)]
-pub fn get_input(input: &str) -> String {
-    input.to_owned()
+pub fn get_input(input: &str) -> Result<Vec<(usize, usize)>, ParseError> {
+    Game::standard().rounds(input, Strategy::Moves)
 }
 
 #[aoc(day2, part1)]
-pub fn part_1(input: &str) -> usize {
-    input.lines().map(|l| Match::from_str_1(l).score()).sum()
+pub fn part_1(rounds: &[(usize, usize)]) -> usize {
+    Game::standard()
+        .score(rounds, Strategy::Moves)
+        .expect("rounds of the standard game")
 }
 
 #[aoc(day2, part2)]
-pub fn part_2(input: &str) -> usize {
-    input.lines().map(|l| Match::from_str_2(l).score()).sum()
+pub fn part_2(rounds: &[(usize, usize)]) -> usize {
+    Game::standard()
+        .score(rounds, Strategy::Outcomes)
+        .expect("rounds of the standard game")
 }
 
+/// The strategy guide's rounds, as the opponent's move and the index of the second column's
+/// letter, which the parts read differently.
+pub struct StrategyGuide(pub Vec<(usize, usize)>);
+
+day!(StrategyGuide.0, 2, usize, usize);
+
+pub static SOLUTIONS: [runner::Day; 1] = [runner::Day::of::<StrategyGuide>()];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     const INPUT: &str = "A Y\nB X\nC Z";
 
+    #[test]
+    fn test_get_input() {
+        assert_eq!(get_input(INPUT), Ok(vec![(0, 1), (1, 0), (2, 2)]));
+        assert_eq!(
+            get_input("A Y\nB W").unwrap_err(),
+            ParseError::new(2, 3, "X, Y or Z").day(2)
+        );
+    }
+
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(&get_input(INPUT)), 15);
+        assert_eq!(part_1(&get_input(INPUT).unwrap()), 15);
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(&get_input(INPUT)), 12);
+        assert_eq!(part_2(&get_input(INPUT).unwrap()), 12);
+    }
+
+    const RPSLS: [&str; 5] = ["rock", "spock", "paper", "lizard", "scissors"];
+
+    #[test]
+    fn test_rules() {
+        let wins = [
+            ("scissors", "paper"),
+            ("paper", "rock"),
+            ("rock", "lizard"),
+            ("lizard", "spock"),
+            ("spock", "scissors"),
+            ("scissors", "lizard"),
+            ("lizard", "paper"),
+            ("paper", "spock"),
+            ("spock", "rock"),
+            ("rock", "scissors"),
+        ];
+        assert_eq!(Rules::cyclic(RPSLS), Rules::from_table(RPSLS, &wins));
+        let rps = Rules::rock_paper_scissors();
+        assert_eq!(rps.outcome(0, 2), Outcome::Win);
+        assert_eq!(rps.outcome(0, 1), Outcome::Lose);
+
+        assert!(Rules::cyclic(["rock", "paper"]).is_err());
+        assert!(Rules::from_table(RPSLS, &wins[1..]).is_err());
+        assert!(Rules::from_table(["a", "b"], &[("a", "b"), ("b", "a")]).is_err());
+        // a beats everything, so there's no losing against it
+        let lopsided = [("a", "b"), ("a", "c"), ("b", "c")];
+        assert!(Rules::from_table(["a", "b", "c"], &lopsided).is_err());
+    }
+
+    #[test]
+    fn test_variants() {
+        let game = Game::new(
+            Rules::cyclic(RPSLS).unwrap(),
+            Scoring {
+                shapes: vec![1, 5, 2, 4, 3],
+                outcomes: [0, 1, 2],
+            },
+            Encoding::new("ABCDE", "VWXYZ", ['L', 'D', 'W']),
+        )
+        .unwrap();
+        // spock (5) beats rock (2 for the win)
+        assert_eq!(game.play("A W", Strategy::Moves), Ok(7));
+        // paper and spock both beat rock, and spock is worth more
+        assert_eq!(game.choose(0, Outcome::Win), 1);
+        assert_eq!(game.play("A W\nE L", Strategy::Outcomes), Ok(7 + 4));
+        assert_eq!(
+            game.play("A X", Strategy::Outcomes),
+            Err(ParseError::new(1, 3, "L, D or W").day(2))
+        );
+        // Z is the fifth move, but there are only three outcomes
+        let rounds = game.rounds("A W\nA Z", Strategy::Moves).unwrap();
+        assert_eq!(game.score(&rounds, Strategy::Moves), Some(7 + 3));
+        assert_eq!(game.score(&rounds, Strategy::Outcomes), None);
+        assert_eq!(game.round(5, 0, Strategy::Moves), None);
+        assert!(Game::standard().mappings(&rounds).is_err());
+
+        let standard = Game::standard();
+        let mismatched = Encoding::new("AB", "XYZ", ['X', 'Y', 'Z']);
+        assert!(Game::new(standard.rules().clone(), Scoring::standard(3), mismatched).is_err());
+        let doubled = Encoding::new("ABC", "XYX", ['X', 'Y', 'Z']);
+        assert!(Game::new(standard.rules().clone(), Scoring::standard(3), doubled).is_err());
+    }
+
+    #[test]
+    fn test_mappings() {
+        let game = Game::standard();
+        let rounds = game.rounds(INPUT, Strategy::Moves).unwrap();
+        let mappings = game.mappings(&rounds).unwrap();
+        assert_eq!(mappings.len(), 6);
+        // winning every round: Y for paper against rock, X for scissors, Z for rock
+        assert_eq!(mappings[0].moves, vec![2, 1, 0]);
+        assert_eq!(
+            (mappings[0].total, mappings[0].mean()),
+            (7 + 8 + 9, Some(8.0))
+        );
+        let part_1 = mappings.iter().find(|m| m.moves == [0, 1, 2]).unwrap();
+        assert_eq!(part_1.total, 15);
+        assert!(game.play(INPUT, Strategy::Outcomes).unwrap() < part_1.total);
+        assert_eq!(game.mappings(&[]).unwrap()[0].mean(), None);
+    }
+
+    fn assert_mix(game: &Game, strategy: &[f64], value: f64) {
+        let equilibrium = game.equilibrium().unwrap();
+        for (p, q) in equilibrium.strategy.iter().zip(strategy) {
+            assert!((p - q).abs() < 1e-9, "{:?}", equilibrium);
+        }
+        assert!(
+            (equilibrium.value - value).abs() < 1e-9,
+            "{:?}",
+            equilibrium
+        );
+    }
+
+    #[test]
+    fn test_equilibrium() {
+        // worked out by hand: the only mix where no move does better than 5 a round against it
+        assert_mix(&Game::standard(), &[4.0 / 9.0, 1.0 / 9.0, 4.0 / 9.0], 5.0);
+
+        // with every move worth the same it's the textbook even mix
+        let even = |rules: Rules, player: &str| {
+            let n = rules.len();
+            let scoring = Scoring {
+                shapes: vec![0; n],
+                outcomes: [0, 3, 6],
+            };
+            let opponent = &"ABCDEFGHIJKLMNOPQRSTUVWXYZ"[..n];
+            Game::new(
+                rules,
+                scoring,
+                Encoding::new(opponent, player, ['X', 'Y', 'Z']),
+            )
+            .unwrap()
+        };
+        assert_mix(
+            &even(Rules::rock_paper_scissors(), "XYZ"),
+            &[1.0 / 3.0; 3],
+            3.0,
+        );
+        assert_mix(
+            &even(Rules::cyclic(RPSLS).unwrap(), "VWXYZ"),
+            &[0.2; 5],
+            3.0,
+        );
+
+        let names: Vec<String> = (0..17).map(|i| i.to_string()).collect();
+        let player = "abcdefghijklmnopq";
+        let big = even(Rules::cyclic(names[..9].to_vec()).unwrap(), &player[..9]);
+        assert!(big.mappings(&[]).is_err());
+        assert!(big.equilibrium().is_ok());
+        let bigger = even(Rules::cyclic(names).unwrap(), player);
+        assert!(bigger.equilibrium().is_err());
     }
 }
@@ -1,66 +1,249 @@
-use std::{collections::HashSet, ops::Div};
+use std::{
+    error::Error,
+    fmt,
+    ops::{BitAnd, BitOr},
+};
+
+use crate::{error::ParseError, runner};
+
+/// A set of items as a bit per priority, from 1 for `a` to 52 for `Z`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Items(u64);
+
+impl Items {
+    pub const EMPTY: Self = Self(0);
+
+    pub fn insert(&mut self, priority: u8) {
+        debug_assert!((1..=52).contains(&priority));
+        self.0 |= 1 << priority;
+    }
+
+    pub fn contains(self, priority: u8) -> bool {
+        priority < 64 && self.0 & 1 << priority != 0
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// The priorities of the items in the set, lowest first.
+    pub fn priorities(self) -> impl Iterator<Item = u8> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            (bits != 0).then(|| {
+                let priority = bits.trailing_zeros() as u8;
+                bits &= bits - 1;
+                priority
+            })
+        })
+    }
+}
+
+impl BitAnd for Items {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        self.intersection(other)
+    }
+}
+
+impl BitOr for Items {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        self.union(other)
+    }
+}
+
+impl FromIterator<u8> for Items {
+    fn from_iter<T: IntoIterator<Item = u8>>(iter: T) -> Self {
+        let mut items = Self::EMPTY;
+        for priority in iter {
+            items.insert(priority);
+        }
+        items
+    }
+}
+
+/// Why there isn't a single item in common.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CommonError {
+    Nothing,
+    Several(Items),
+    /// A rucksack whose items don't split evenly into the compartments.
+    Uneven {
+        items: usize,
+        compartments: usize,
+    },
+    /// The last group, with fewer rucksacks than the rest.
+    ShortGroup {
+        size: usize,
+        rucksacks: usize,
+    },
+    /// Asking for no compartments, or for groups of no rucksacks.
+    Zero,
+}
+
+impl fmt::Display for CommonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Nothing => write!(f, "no item in common"),
+            Self::Several(items) => write!(f, "{} items in common", items.len()),
+            Self::Uneven {
+                items,
+                compartments,
+            } => write!(
+                f,
+                "{} items don't go into {} compartments",
+                items, compartments
+            ),
+            Self::ShortGroup { size, rucksacks } => {
+                write!(f, "a group of {} with only {} rucksacks", size, rucksacks)
+            }
+            Self::Zero => write!(f, "nothing to split into"),
+        }
+    }
+}
+
+impl Error for CommonError {}
+
+/// The items that are in every one of `sets`.
+pub fn common_items(sets: impl IntoIterator<Item = Items>) -> Items {
+    let mut sets = sets.into_iter();
+    let first = sets.next().unwrap_or(Items::EMPTY);
+    sets.fold(first, BitAnd::bitand)
+}
+
+/// The priority of the only item that's in every one of `sets`.
+pub fn common(sets: impl IntoIterator<Item = Items>) -> Result<u8, CommonError> {
+    let items = common_items(sets);
+    match items.len() {
+        0 => Err(CommonError::Nothing),
+        1 => Ok(items.priorities().next().unwrap()),
+        _ => Err(CommonError::Several(items)),
+    }
+}
 
 #[derive(Debug)]
 pub struct Rucksack {
+    /// By priority, in order.
     items: Vec<u8>,
+    set: Items,
 }
 
 impl Rucksack {
-    fn overlap(&self) -> usize {
-        let (left, right) = self.items.split_at(self.items.len().div(2));
-        left.iter()
-            .cloned()
-            .collect::<HashSet<u8>>()
-            .intersection(&right.iter().cloned().collect::<HashSet<u8>>())
-            .map(|u| usize::from(*u))
-            .sum()
+    pub fn items(&self) -> Items {
+        self.set
     }
 
-    fn items_set(&self) -> HashSet<u8> {
-        self.items.iter().cloned().collect()
+    /// The items in each of `n` equal compartments.
+    pub fn compartments(&self, n: usize) -> Result<Vec<Items>, CommonError> {
+        if n == 0 {
+            return Err(CommonError::Zero);
+        }
+        if !self.items.len().is_multiple_of(n) {
+            return Err(CommonError::Uneven {
+                items: self.items.len(),
+                compartments: n,
+            });
+        }
+        Ok(self
+            .items
+            .chunks(self.items.len() / n)
+            .map(|c| c.iter().copied().collect())
+            .collect())
+    }
+
+    /// The priority of the item that's in both compartments.
+    pub fn misplaced(&self) -> Result<u8, CommonError> {
+        common(self.compartments(2)?)
     }
 }
 
-impl From<&str> for Rucksack {
-    fn from(s: &str) -> Self {
-        Self {
-            items: s
-                .as_bytes()
-                .iter()
-                .map(|&c| if c > 96 { c - 96 } else { c - 38 })
-                .collect::<Vec<u8>>(),
+impl TryFrom<&str> for Rucksack {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        if let Some(i) = s.find(|c: char| !c.is_ascii_alphabetic()) {
+            return Err(ParseError::at(s, &s[i..], "item letter").day(3));
         }
+        let items: Vec<u8> = s
+            .as_bytes()
+            .iter()
+            .map(|&c| if c > 96 { c - 96 } else { c - 38 })
+            .collect();
+        let set = items.iter().copied().collect();
+        Ok(Self { items, set })
     }
 }
 
+/// The badge of each group of `size` rucksacks in a row, i.e. the one item they all have.
+pub fn badges(
+    rucksacks: &[Rucksack],
+    size: usize,
+) -> Result<impl Iterator<Item = Result<u8, CommonError>> + '_, CommonError> {
+    if size == 0 {
+        return Err(CommonError::Zero);
+    }
+    Ok(rucksacks.chunks(size).map(move |group| {
+        if group.len() < size {
+            return Err(CommonError::ShortGroup {
+                size,
+                rucksacks: group.len(),
+            });
+        }
+        common(group.iter().map(Rucksack::items))
+    }))
+}
+
 #[aoc_generator(day3)]
-pub fn get_input(input: &str) -> Vec<Rucksack> {
-    input.lines().map(Rucksack::from).collect()
+pub fn get_input(input: &str) -> Result<Vec<Rucksack>, ParseError> {
+    input
+        .lines()
+        .map(|l| Rucksack::try_from(l).map_err(|e| e.within(input, l)))
+        .collect()
 }
 
 #[aoc(day3, part1)]
 pub fn part_1(rucksacks: &[Rucksack]) -> usize {
-    rucksacks.iter().map(|r| r.overlap()).sum()
+    rucksacks
+        .iter()
+        .enumerate()
+        .map(|(i, r)| {
+            let item = r.misplaced();
+            usize::from(item.unwrap_or_else(|e| panic!("rucksack {}: {}", i + 1, e)))
+        })
+        .sum()
 }
 
 #[aoc(day3, part2)]
 pub fn part_2(rucksacks: &[Rucksack]) -> usize {
-    rucksacks
-        .chunks(3)
-        .map(|w| {
-            usize::from(
-                *w.iter()
-                    .fold(w[0].items_set(), |acc, x| {
-                        acc.intersection(&x.items_set()).cloned().collect()
-                    })
-                    .iter()
-                    .next()
-                    .unwrap(),
-            )
-        })
+    badges(rucksacks, 3)
+        .unwrap()
+        .enumerate()
+        .map(|(i, badge)| usize::from(badge.unwrap_or_else(|e| panic!("group {}: {}", i + 1, e))))
         .sum()
 }
 
+/// Each rucksack's items, in order, since part 2 groups them in threes.
+pub struct Rucksacks(pub Vec<Rucksack>);
+
+day!(Rucksacks.0, 3, usize, usize);
+
+pub static SOLUTIONS: [runner::Day; 1] = [runner::Day::of::<Rucksacks>()];
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -74,11 +257,61 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(&get_input(INPUT)), 157);
+        assert_eq!(part_1(&get_input(INPUT).unwrap()), 157);
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(&get_input(INPUT)), 70);
+        assert_eq!(part_2(&get_input(INPUT).unwrap()), 70);
+    }
+
+    #[test]
+    fn test_items() {
+        let a: Items = [1, 3, 52].into_iter().collect();
+        let b: Items = [3, 4, 52].into_iter().collect();
+        assert_eq!((a & b).priorities().collect::<Vec<_>>(), vec![3, 52]);
+        assert_eq!((a | b).len(), 4);
+        assert!(a.contains(52) && !a.contains(4) && !a.contains(200));
+        assert!(Items::EMPTY.is_empty());
+        assert_eq!(common_items([]), Items::EMPTY);
+    }
+
+    #[test]
+    fn test_common() {
+        let rucksacks = get_input(INPUT).unwrap();
+        // p, L, P, v, t and s
+        let misplaced: Vec<u8> = rucksacks.iter().map(|r| r.misplaced().unwrap()).collect();
+        assert_eq!(misplaced, vec![16, 38, 42, 22, 20, 19]);
+        // r and Z
+        let threes: Result<Vec<u8>, _> = badges(&rucksacks, 3).unwrap().collect();
+        assert_eq!(threes, Ok(vec![18, 52]));
+
+        assert_eq!(
+            badges(&rucksacks[..4], 2).unwrap().collect::<Vec<_>>()[1],
+            Err(CommonError::Several(
+                rucksacks[2].items() & rucksacks[3].items()
+            ))
+        );
+        assert_eq!(
+            badges(&rucksacks, 4).unwrap().nth(1),
+            Some(Err(CommonError::ShortGroup {
+                size: 4,
+                rucksacks: 2
+            }))
+        );
+        assert_eq!(
+            rucksacks[0].compartments(5),
+            Err(CommonError::Uneven {
+                items: 24,
+                compartments: 5
+            })
+        );
+        assert_eq!(rucksacks[0].compartments(0), Err(CommonError::Zero));
+        assert!(matches!(badges(&rucksacks, 0), Err(CommonError::Zero)));
+        // vJrwpWtw, JgWrhcsF, MMfFFhFp
+        assert_eq!(
+            common(rucksacks[0].compartments(3).unwrap()),
+            Err(CommonError::Nothing)
+        );
     }
 }
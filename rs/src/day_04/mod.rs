@@ -1,17 +1,25 @@
 use sscanf::sscanf;
 
+use crate::{error::ParseError, interval::IntervalSet, runner};
+
+/// The sections an elf is assigned, which the input gives as a closed range like `2-4`.
 struct Elf {
-    a: usize,
-    b: usize,
+    sections: IntervalSet<usize>,
 }
 
 impl Elf {
+    fn new(a: usize, b: usize) -> Self {
+        let mut sections = IntervalSet::new();
+        sections.insert_closed(a..=b);
+        Self { sections }
+    }
+
     fn fully_contains(&self, other: &Elf) -> bool {
-        self.a <= other.a && self.b >= other.b
+        self.sections.is_superset(&other.sections)
     }
 
     fn overlaps(&self, other: &Elf) -> bool {
-        self.a <= other.b && self.b >= other.a
+        self.sections.overlaps(&other.sections)
     }
 }
 
@@ -30,26 +38,38 @@ impl ElfPair {
     }
 }
 
-impl From<&str> for ElfPair {
-    fn from(s: &str) -> Self {
+impl TryFrom<&str> for ElfPair {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
         let (elf_1_a, elf_1_b, elf_2_a, elf_2_b) =
-            sscanf!(s, "{}-{},{}-{}", usize, usize, usize, usize).unwrap();
-        ElfPair {
-            elf_1: Elf {
-                a: elf_1_a,
-                b: elf_1_b,
-            },
-            elf_2: Elf {
-                a: elf_2_a,
-                b: elf_2_b,
-            },
+            sscanf!(s, "{}-{},{}-{}", usize, usize, usize, usize)
+                .map_err(|_| ParseError::at(s, s, "pair of ranges like `2-4,6-8`").day(4))?;
+        // the second elf's range starts after the comma
+        let second = &s[s.find(',').unwrap() + 1..];
+        for (range, a, b) in [(s, elf_1_a, elf_1_b), (second, elf_2_a, elf_2_b)] {
+            if a > b {
+                return Err(ParseError::at(s, range, "range that doesn't go backwards").day(4));
+            }
+            // stored half-open, so the end needs a section after it
+            if b == usize::MAX {
+                let end = &range[range.find('-').unwrap() + 1..];
+                return Err(ParseError::at(s, end, "section below `usize::MAX`").day(4));
+            }
         }
+        Ok(ElfPair {
+            elf_1: Elf::new(elf_1_a, elf_1_b),
+            elf_2: Elf::new(elf_2_a, elf_2_b),
+        })
     }
 }
 
 #[aoc_generator(day4)]
-pub fn get_input(input: &str) -> Vec<ElfPair> {
-    input.lines().map(ElfPair::from).collect()
+pub fn get_input(input: &str) -> Result<Vec<ElfPair>, ParseError> {
+    input
+        .lines()
+        .map(|l| ElfPair::try_from(l).map_err(|e| e.within(input, l)))
+        .collect()
 }
 
 #[aoc(day4, part1)]
@@ -65,9 +85,19 @@ pub fn part_2(elf_pairs: &[ElfPair]) -> usize {
     elf_pairs.iter().filter(|&pair| pair.overlaps()).count()
 }
 
+/// The section assignments, one pair of elves per line.
+pub struct Assignments(pub Vec<ElfPair>);
+
+day!(Assignments.0, 4, usize, usize);
+
+pub static SOLUTIONS: [runner::Day; 1] = [runner::Day::of::<Assignments>()];
+
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
+    use crate::inputgen;
 
     const INPUT: &str = "2-4,6-8\n\
         2-3,4-5\n\
@@ -78,11 +108,34 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(&get_input(INPUT)), 2);
+        assert_eq!(part_1(&get_input(INPUT).unwrap()), 2);
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(&get_input(INPUT)), 4);
+        assert_eq!(part_2(&get_input(INPUT).unwrap()), 4);
+    }
+
+    #[test]
+    fn test_backwards_range() {
+        let err = get_input("2-4,6-8\n2-4,8-6").err().unwrap();
+        assert_eq!((err.day, err.line, err.column), (Some(4), 2, 5));
+        assert_eq!(get_input("4-2,1-1").err().unwrap().column, 1);
+    }
+
+    #[test]
+    fn test_max_section() {
+        let err = get_input("0-18446744073709551615,1-2").err().unwrap();
+        assert_eq!((err.line, err.column), (1, 3));
+        let err = get_input("1-2,0-18446744073709551615").err().unwrap();
+        assert_eq!((err.line, err.column), (1, 7));
+    }
+
+    proptest! {
+        #[test]
+        fn test_containing_overlaps(input in inputgen::arbitrary(4, 1..=100)) {
+            let pairs = get_input(&input).unwrap();
+            prop_assert!(part_2(&pairs) >= part_1(&pairs));
+        }
     }
 }
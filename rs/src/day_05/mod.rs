@@ -2,6 +2,8 @@ use std::{cell::RefCell, rc::Rc};
 
 use sscanf::scanf;
 
+use crate::{error::ParseError, runner};
+
 #[derive(Debug, Clone)]
 pub struct Cargo {
     stacks: Vec<Rc<RefCell<Vec<char>>>>,
@@ -38,38 +40,53 @@ impl Cargo {
 }
 
 impl TryFrom<&str> for Cargo {
-    type Error = &'static str;
+    type Error = ParseError;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
-        let (stacks_chunk, moves_chunk) = s.split_once("\n\n").ok_or("parse error")?;
+        let err = |rest: &str, expected: &str| ParseError::at(s, rest, expected).day(5);
+        let (stacks_chunk, moves_chunk) = s
+            .split_once("\n\n")
+            .ok_or_else(|| err(&s[s.len()..], "blank line after the stacks"))?;
         let mut stacks_lines: Vec<&str> = stacks_chunk.lines().collect();
-        let numbers_line = stacks_lines.pop().ok_or("bad input")?;
+        let numbers_line = stacks_lines
+            .pop()
+            .ok_or_else(|| err(stacks_chunk, "stack numbers"))?;
         let n = numbers_line
             .split_whitespace()
             .last()
             .and_then(|x| x.parse::<usize>().ok())
-            .ok_or("parse error")?;
+            .ok_or_else(|| err(numbers_line, "stack numbers"))?;
         let mut stacks = Vec::new();
         (0..n).for_each(|_| stacks.push(Rc::new(RefCell::new(Vec::new()))));
         for line in stacks_lines.iter().rev() {
             for (i, chunk) in line.as_bytes().chunks(4).enumerate() {
                 if chunk.first() == Some(&b'[') {
-                    (*stacks[i]).borrow_mut().push(chunk[1] as char);
+                    let stack = stacks
+                        .get(i)
+                        .ok_or_else(|| err(&line[4 * i..], "crate within a numbered stack"))?;
+                    let c = chunk
+                        .get(1)
+                        .ok_or_else(|| err(&line[4 * i + 1..], "crate letter"))?;
+                    (**stack).borrow_mut().push(*c as char);
                 }
             }
         }
         let moves: Vec<(usize, usize, usize)> = moves_chunk
             .lines()
-            .map(|l| scanf!(l, "move {} from {} to {}", usize, usize, usize))
-            .collect::<Result<Vec<(usize, usize, usize)>, _>>()
-            .map_err(|_| "parse error")?;
+            .map(|l| {
+                scanf!(l, "move {} from {} to {}", usize, usize, usize)
+                    .ok()
+                    .filter(|&(_, from, to)| (1..=n).contains(&from) && (1..=n).contains(&to))
+                    .ok_or_else(|| err(l, "`move N from A to B` between numbered stacks"))
+            })
+            .collect::<Result<Vec<(usize, usize, usize)>, _>>()?;
         Ok(Cargo { stacks, moves })
     }
 }
 
 #[aoc_generator(day5)]
-pub fn get_input(input: &str) -> Cargo {
-    input.try_into().unwrap()
+pub fn get_input(input: &str) -> Result<Cargo, ParseError> {
+    input.try_into()
 }
 
 #[aoc(day5, part1)]
@@ -82,19 +99,32 @@ pub fn part_2(cargo: &Cargo) -> String {
     cargo.clone().execute_moves_2().tops()
 }
 
+day!(Cargo, 5, String, String);
+
+pub static SOLUTIONS: [runner::Day; 1] = [runner::Day::of::<Cargo>()];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     const INPUT: &str = include_str!("../../test_data/day_05.txt");
 
+    #[test]
+    fn test_get_input() {
+        let bad_move = INPUT.replace("move 1 from 1 to 2", "move 1 from 1 to 4");
+        assert_eq!(
+            get_input(&bad_move).unwrap_err(),
+            ParseError::new(9, 1, "`move N from A to B` between numbered stacks").day(5)
+        );
+    }
+
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(&get_input(INPUT)), "CMZ");
+        assert_eq!(part_1(&get_input(INPUT).unwrap()), "CMZ");
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(&get_input(INPUT)), "MCD");
+        assert_eq!(part_2(&get_input(INPUT).unwrap()), "MCD");
     }
 }
@@ -1,22 +1,28 @@
+use crate::{error::ParseError, runner};
+
 fn first_marker(s: &String, k: usize) -> Option<usize> {
     let bs = s.as_bytes();
     let mut mask: u32 = bs
         .iter()
         .take(k)
-        .fold(0, |acc: u32, c| acc ^ 1 << (*c as u8 - b'a'));
+        .fold(0, |acc: u32, c| acc ^ 1 << (*c - b'a'));
     for (i, (add, remove)) in bs.iter().skip(k).zip(bs.iter()).enumerate() {
         if mask.count_ones() as usize == k {
             return Some(i + k);
         }
-        mask ^= 1 << (*add as u8 - b'a');
-        mask ^= 1 << (*remove as u8 - b'a');
+        mask ^= 1 << (*add - b'a');
+        mask ^= 1 << (*remove - b'a');
     }
-    None
+    // the loop never gets to check the last window
+    (mask.count_ones() as usize == k).then_some(bs.len())
 }
 
 #[aoc_generator(day6)]
-pub fn get_input(input: &str) -> String {
-    input.to_owned()
+pub fn get_input(input: &str) -> Result<String, ParseError> {
+    match input.find(|c: char| !c.is_ascii_lowercase()) {
+        Some(i) => Err(ParseError::at(input, &input[i..], "lowercase letter").day(6)),
+        None => Ok(input.to_owned()),
+    }
 }
 
 #[aoc(day6, part1)]
@@ -29,25 +35,63 @@ pub fn part_2(s: &String) -> usize {
     first_marker(s, 14).unwrap()
 }
 
+/// The datastream buffer.
+pub struct Datastream(pub String);
+
+day!(Datastream.0, 6, usize, usize);
+
+pub static SOLUTIONS: [runner::Day; 1] = [runner::Day::of::<Datastream>()];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(&get_input("mjqjpqmgbljsphdztnvjfqwrcgsmlb")), 7);
-        assert_eq!(part_1(&get_input("bvwbjplbgvbhsrlpgdmjqwftvncz")), 5);
-        assert_eq!(part_1(&get_input("nppdvjthqldpwncqszvftbrmjlhg")), 6);
-        assert_eq!(part_1(&get_input("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg")), 10);
-        assert_eq!(part_1(&get_input("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw")), 11);
+        assert_eq!(
+            part_1(&get_input("mjqjpqmgbljsphdztnvjfqwrcgsmlb").unwrap()),
+            7
+        );
+        assert_eq!(
+            part_1(&get_input("bvwbjplbgvbhsrlpgdmjqwftvncz").unwrap()),
+            5
+        );
+        assert_eq!(
+            part_1(&get_input("nppdvjthqldpwncqszvftbrmjlhg").unwrap()),
+            6
+        );
+        assert_eq!(
+            part_1(&get_input("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg").unwrap()),
+            10
+        );
+        assert_eq!(
+            part_1(&get_input("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw").unwrap()),
+            11
+        );
+        assert_eq!(part_1(&get_input("aaabcd").unwrap()), 6);
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(&get_input("mjqjpqmgbljsphdztnvjfqwrcgsmlb")), 19);
-        assert_eq!(part_2(&get_input("bvwbjplbgvbhsrlpgdmjqwftvncz")), 23);
-        assert_eq!(part_2(&get_input("nppdvjthqldpwncqszvftbrmjlhg")), 23);
-        assert_eq!(part_2(&get_input("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg")), 29);
-        assert_eq!(part_2(&get_input("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw")), 26);
+        assert_eq!(
+            part_2(&get_input("mjqjpqmgbljsphdztnvjfqwrcgsmlb").unwrap()),
+            19
+        );
+        assert_eq!(
+            part_2(&get_input("bvwbjplbgvbhsrlpgdmjqwftvncz").unwrap()),
+            23
+        );
+        assert_eq!(
+            part_2(&get_input("nppdvjthqldpwncqszvftbrmjlhg").unwrap()),
+            23
+        );
+        assert_eq!(
+            part_2(&get_input("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg").unwrap()),
+            29
+        );
+        assert_eq!(
+            part_2(&get_input("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw").unwrap()),
+            26
+        );
     }
 }
@@ -1,5 +1,7 @@
 use std::{cell::RefCell, collections::HashMap, fmt::Formatter, rc::Rc};
 
+use crate::{error::ParseError, runner};
+
 #[derive(Debug, PartialEq)]
 enum DirToken {
     Root,
@@ -24,7 +26,7 @@ struct LSOutput {
 }
 
 impl TryFrom<&str> for LSOutput {
-    type Error = String;
+    type Error = ParseError;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
         let mut dirs = Vec::new();
@@ -32,11 +34,15 @@ impl TryFrom<&str> for LSOutput {
         for line in s.lines() {
             let (p1, p2) = line
                 .split_once(' ')
-                .ok_or(format!("parse error on line: {}", line))?;
+                .ok_or_else(|| ParseError::at(s, line, "`dir NAME` or `SIZE NAME`").day(7))?;
             if p1 == "dir" {
                 dirs.push(p2.to_string());
             } else {
-                files.insert(p2.to_string(), p1.parse().map_err(|_| "parse error: size")?);
+                files.insert(
+                    p2.to_string(),
+                    p1.parse()
+                        .map_err(|_| ParseError::at(s, line, "file size").day(7))?,
+                );
             }
         }
         Ok(Self { dirs, files })
@@ -49,17 +55,18 @@ enum Command {
 }
 
 impl TryFrom<&str> for Command {
-    type Error = String;
+    type Error = ParseError;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
         if let Some(dir) = s.strip_prefix("cd ") {
             Ok(Command::CD(dir.trim().into()))
+        } else if s == "ls" || s.starts_with("ls\n") {
+            let output = s.get(3..).unwrap_or("");
+            LSOutput::try_from(output)
+                .map(Command::LS)
+                .map_err(|e| e.within(s, output))
         } else {
-            Ok(Command::LS(LSOutput::try_from(
-                s.split_once('\n')
-                    .ok_or(format!("parse error in command: {}", s))?
-                    .1,
-            )?))
+            Err(ParseError::at(s, s, "`cd` or `ls` command").day(7))
         }
     }
 }
@@ -134,14 +141,16 @@ impl DirTreeOwner {
         result
     }
 
-    fn size_of_small_dirs(&self) -> usize {
+    /// The total size of the directories of at most 100000, counting nested ones again.
+    pub fn size_of_small_dirs(&self) -> usize {
         self.nodes()
             .iter()
             .filter_map(|n| Some(n.borrow_mut().size()).filter(|&size| size < 100000))
             .sum()
     }
 
-    fn smallest_dir_to_delete(&self, total: usize, need: usize) -> usize {
+    /// The size of the smallest directory that would leave `need` free out of `total` if deleted.
+    pub fn smallest_dir_to_delete(&self, total: usize, need: usize) -> usize {
         let usage: usize = self.root.borrow_mut().size();
         self.nodes().iter().fold(usize::MAX, |acc, node| {
             let size = node.borrow_mut().size();
@@ -155,16 +164,23 @@ impl DirTreeOwner {
 }
 
 impl TryFrom<&str> for DirTreeOwner {
-    type Error = String;
+    type Error = ParseError;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
         let owner = DirTreeOwner::new();
-        s.split("$ ")
-            .skip(1) // empty item since input starts with $
-            .map(Command::try_from)
-            .collect::<Result<Vec<Command>, String>>()?
+        let mut chunks = s.split("$ ");
+        if let Some(before) = chunks.next().filter(|before| !before.is_empty()) {
+            return Err(ParseError::at(s, before, "`$ ` before the first command").day(7));
+        }
+        chunks
+            .map(|chunk| {
+                Command::try_from(chunk)
+                    .map(|cmd| (chunk, cmd))
+                    .map_err(|e| e.within(s, chunk))
+            })
+            .collect::<Result<Vec<(&str, Command)>, ParseError>>()?
             .iter()
-            .try_fold(owner.root.clone(), |current, cmd| match cmd {
+            .try_fold(owner.root.clone(), |current, (chunk, cmd)| match cmd {
                 Command::CD(dir) => match dir {
                     DirToken::Root => Ok(owner.root.clone()),
                     DirToken::Parent => {
@@ -172,7 +188,7 @@ impl TryFrom<&str> for DirTreeOwner {
                         if let Some(p) = parent {
                             Ok(p)
                         } else {
-                            Err("cd .. from root")
+                            Err(ParseError::at(s, chunk, "`cd ..` below the root").day(7))
                         }
                     }
                     DirToken::Dir(dir) => Ok(current
@@ -196,8 +212,8 @@ impl TryFrom<&str> for DirTreeOwner {
 }
 
 #[aoc_generator(day7)]
-pub fn get_input(input: &str) -> DirTreeOwner {
-    input.try_into().unwrap()
+pub fn get_input(input: &str) -> Result<DirTreeOwner, ParseError> {
+    input.try_into()
 }
 
 #[aoc(day7, part1)]
@@ -210,6 +226,10 @@ pub fn part_2(tree: &DirTreeOwner) -> usize {
     tree.smallest_dir_to_delete(70000000, 30000000)
 }
 
+day!(DirTreeOwner, 7, usize, usize);
+
+pub static SOLUTIONS: [runner::Day; 1] = [runner::Day::of::<DirTreeOwner>()];
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -236,7 +256,7 @@ mod tests {
             .collect(),
         };
         assert_eq!(LSOutput::try_from(ls_in).unwrap(), ls_out);
-        assert!(DirTreeOwner::try_from("cd /")
+        assert!(DirTreeOwner::try_from("$ cd /")
             .unwrap()
             .root
             .borrow()
@@ -246,11 +266,11 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(&get_input(INPUT)), 95437);
+        assert_eq!(part_1(&get_input(INPUT).unwrap()), 95437);
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(&get_input(INPUT)), 24933642);
+        assert_eq!(part_2(&get_input(INPUT).unwrap()), 24933642);
     }
 }
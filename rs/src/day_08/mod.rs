@@ -1,6 +1,8 @@
 use std::collections::HashSet;
 
-fn visible_indices<T>(v: &Vec<T>) -> HashSet<usize>
+use crate::{error::ParseError, grid::Grid, runner, solution::Alternate};
+
+fn visible_indices<T>(v: &[T]) -> HashSet<usize>
 where
     T: Ord,
 {
@@ -42,109 +44,125 @@ impl Scenery {
     }
 }
 
-fn sceneries_for(vs: &Vec<Vec<usize>>) -> Vec<Vec<Scenery>> {
-    if vs.is_empty() {
-        return Vec::new();
-    }
-    let mut results: Vec<Vec<Scenery>> = vs
+fn sceneries_for(trees: &Grid<usize>) -> Vec<Scenery> {
+    let (w, h) = (trees.w, trees.h());
+    let i = |x: usize, y: usize| trees.xy_to_i((x, y));
+    let mut results: Vec<Scenery> = trees
+        .items
         .iter()
-        .map(|v| {
-            v.iter()
-                .map(|h| Scenery {
-                    here: *h,
-                    up: [0; 10],
-                    down: [0; 10],
-                    left: [0; 10],
-                    right: [0; 10],
-                })
-                .collect()
+        .map(|&here| Scenery {
+            here,
+            up: [0; 10],
+            down: [0; 10],
+            left: [0; 10],
+            right: [0; 10],
         })
         .collect();
-    // e.g. results[i][0].left stays zeroed.
+    // e.g. the left edge's .left stays zeroed.
     // At each step going "right", copy the previous LEFT DistToHeight forwards, and update the
     // distance for the immediately previous tree's height.
-    for i in 0..vs.len() {
-        for j in 1..vs.len() {
-            for h in 0..=9 {
-                results[i][j].left[h] = results[i][j - 1].left[h] + 1;
+    for y in 0..h {
+        for x in 1..w {
+            for t in 0..=9 {
+                results[i(x, y)].left[t] = results[i(x - 1, y)].left[t] + 1;
             }
-            (0..=vs[i][j - 1]).for_each(|h| results[i][j].left[h] = 1);
+            (0..=trees[i(x - 1, y)]).for_each(|t| results[i(x, y)].left[t] = 1);
             // same going in opposite direction
-            for h in 0..=9 {
-                results[i][vs.len() - j - 1].right[h] = results[i][vs.len() - j].right[h] + 1;
+            for t in 0..=9 {
+                results[i(w - x - 1, y)].right[t] = results[i(w - x, y)].right[t] + 1;
             }
-            (0..=vs[i][vs.len() - j]).for_each(|h| results[i][vs.len() - j - 1].right[h] = 1);
-            // now again for up and down
-            for h in 0..=9 {
-                results[j][i].up[h] = results[j - 1][i].up[h] + 1;
+            (0..=trees[i(w - x, y)]).for_each(|t| results[i(w - x - 1, y)].right[t] = 1);
+        }
+    }
+    // now again for up and down
+    for x in 0..w {
+        for y in 1..h {
+            for t in 0..=9 {
+                results[i(x, y)].up[t] = results[i(x, y - 1)].up[t] + 1;
             }
-            (0..=vs[j - 1][i]).for_each(|h| results[j][i].up[h] = 1);
-            for h in 0..=9 {
-                results[vs.len() - j - 1][i].down[h] = results[vs.len() - j][i].down[h] + 1;
+            (0..=trees[i(x, y - 1)]).for_each(|t| results[i(x, y)].up[t] = 1);
+            for t in 0..=9 {
+                results[i(x, h - y - 1)].down[t] = results[i(x, h - y)].down[t] + 1;
             }
-            (0..=vs[vs.len() - j][i]).for_each(|h| results[vs.len() - j - 1][i].down[h] = 1)
+            (0..=trees[i(x, h - y)]).for_each(|t| results[i(x, h - y - 1)].down[t] = 1)
         }
     }
     results
 }
 
-fn transpose<T>(v: Vec<Vec<T>>) -> Vec<Vec<T>> {
-    if v.is_empty() {
-        return Vec::new();
-    }
-    let len = v[0].len();
-    let mut iters: Vec<_> = v.into_iter().map(|n| n.into_iter()).collect();
-    (0..len)
-        .map(|_| {
-            iters
-                .iter_mut()
-                .map(|n| n.next().unwrap())
-                .collect::<Vec<T>>()
+/// Part 1 from the same sceneries as part 2. Looking from a tree towards an edge, the distance to
+/// the nearest tree at least as tall is the distance to the edge both when there's no such tree
+/// and when the edge tree is one, so the edge tree settles which.
+pub fn part_1_by_sceneries(trees: &Grid<usize>) -> usize {
+    let (w, h) = (trees.w, trees.h());
+    sceneries_for(trees)
+        .iter()
+        .enumerate()
+        .filter(|(i, s)| {
+            let (x, y) = trees.i_to_xy(*i);
+            let clear = |dist: &DistToHeight, to_edge: usize, edge: (usize, usize)| {
+                to_edge == 0 || (dist[s.here] == to_edge && trees[trees.xy_to_i(edge)] < s.here)
+            };
+            clear(&s.left, x, (0, y))
+                || clear(&s.right, w - 1 - x, (w - 1, y))
+                || clear(&s.up, y, (x, 0))
+                || clear(&s.down, h - 1 - y, (x, h - 1))
         })
-        .collect()
+        .count()
 }
 
 #[aoc_generator(day8)]
-pub fn get_input(input: &str) -> Vec<Vec<usize>> {
-    input
-        .trim()
-        .split('\n')
-        .map(|s| {
-            s.chars()
-                .map(|c| c.to_digit(10).unwrap() as usize)
-                .collect()
-        })
-        .collect()
+pub fn get_input(input: &str) -> Result<Grid<usize>, ParseError> {
+    Grid::parse_with(input.trim(), None, |c| {
+        c.to_digit(10)
+            .map(|d| d as usize)
+            .ok_or_else(|| "tree height digit".to_owned())
+    })
+    .map_err(|e| e.within(input, input.trim()).day(8))
 }
 
 #[aoc(day8, part1)]
-pub fn part_1(trees: &[Vec<usize>]) -> usize {
-    // TODO: could reimplement using sceneries_for
+pub fn part_1(trees: &Grid<usize>) -> usize {
     let mut visible: HashSet<(usize, usize)> = HashSet::new();
     visible.extend(
         trees
-            .iter()
+            .rows()
             .enumerate()
-            .flat_map(|(i, line)| visible_indices(line).into_iter().map(move |j| (i, j))),
+            .flat_map(|(y, row)| visible_indices(row).into_iter().map(move |x| (x, y))),
     );
     visible.extend(
-        transpose(trees.to_owned())
-            .iter()
+        trees
+            .transpose()
+            .rows()
             .enumerate()
-            .flat_map(|(i, line)| visible_indices(line).into_iter().map(move |j| (j, i))),
+            .flat_map(|(x, column)| visible_indices(column).into_iter().map(move |y| (x, y))),
     );
     visible.len()
 }
 
 #[aoc(day8, part2)]
-pub fn part_2(trees: &Vec<Vec<usize>>) -> usize {
+pub fn part_2(trees: &Grid<usize>) -> usize {
     sceneries_for(trees)
         .iter()
-        .map(|v| v.iter().map(|s| s.score()).max().unwrap())
+        .map(|s| s.score())
         .max()
         .unwrap()
 }
 
+/// Each tree's height.
+pub struct Forest(pub Grid<usize>);
+
+day! {
+    Forest.0, 8, usize, usize;
+    const ALTERNATES: &'static [Alternate<Self>] = &[Alternate {
+        part: 1,
+        name: "sceneries",
+        solve: |forest| part_1_by_sceneries(&forest.0).into(),
+    }];
+}
+
+pub static SOLUTIONS: [runner::Day; 1] = [runner::Day::of::<Forest>()];
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -153,11 +171,25 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(&get_input(INPUT)), 21);
+        assert_eq!(part_1(&get_input(INPUT).unwrap()), 21);
+        assert_eq!(part_1_by_sceneries(&get_input(INPUT).unwrap()), 21);
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(&get_input(INPUT)), 8);
+        assert_eq!(part_2(&get_input(INPUT).unwrap()), 8);
+    }
+
+    #[test]
+    fn test_not_square() {
+        let trees = get_input("30373\n25512\n65332\n").unwrap();
+        assert_eq!(part_1(&trees), 14);
+        assert_eq!(part_1_by_sceneries(&trees), 14);
+        assert_eq!(part_1_by_sceneries(&get_input("5\n").unwrap()), 1);
+        assert_eq!(part_2(&trees), 2);
+        assert_eq!(
+            get_input("303\n2551\n"),
+            Err(ParseError::new(2, 1, "row of width 3").day(8))
+        );
     }
 }
@@ -3,18 +3,27 @@ use std::collections::{HashMap, HashSet};
 use num::Complex;
 use sscanf::scanf;
 
+use crate::{
+    error::ParseError,
+    record::{Discard, Recorder},
+    render::Canvas,
+    runner,
+    solution::Alternate,
+};
+
 #[aoc_generator(day9)]
-pub fn get_input(input: &str) -> Vec<Complex<isize>> {
+pub fn get_input(input: &str) -> Result<Vec<Complex<isize>>, ParseError> {
     input
         .lines()
         .map(|l| {
-            let (dir, dist) = scanf!(l, "{} {}", char, isize).unwrap();
+            let (dir, dist) = scanf!(l, "{} {}", char, isize)
+                .map_err(|_| ParseError::at(input, l, "direction and distance").day(9))?;
             match dir {
-                'L' => Complex::new(-dist, 0),
-                'R' => Complex::new(dist, 0),
-                'U' => Complex::new(0, dist),
-                'D' => Complex::new(0, -dist),
-                _ => panic!("Invalid direction"),
+                'L' => Ok(Complex::new(-dist, 0)),
+                'R' => Ok(Complex::new(dist, 0)),
+                'U' => Ok(Complex::new(0, dist)),
+                'D' => Ok(Complex::new(0, -dist)),
+                _ => Err(ParseError::at(input, l, "L, R, U or D").day(9)),
             }
         })
         .collect()
@@ -66,10 +75,30 @@ impl LongRope {
         }
     }
 
+    /// Everywhere the tracked knots have been as `#`, and the knots themselves as `H` for the
+    /// head and digits for the rest.
+    fn draw(&self, visited: &HashMap<usize, HashSet<Complex<isize>>>) -> Canvas {
+        // up is positive in the puzzle, but down is positive on the canvas
+        let xy = |c: &Complex<isize>| (c.re, -c.im);
+        let points = visited.values().flatten().chain(self.knots.iter());
+        let mut canvas = Canvas::from_points(points.map(xy), '#', '.');
+        canvas.highlight([(0, 0)], 's');
+        for (i, knot) in self.knots.iter().enumerate().rev() {
+            let label = if i == 0 {
+                'H'
+            } else {
+                char::from_digit(i as u32 % 10, 10).unwrap()
+            };
+            canvas.highlight([xy(knot)], label);
+        }
+        canvas
+    }
+
     fn tail_positions(
         &mut self,
         moves: &[Complex<isize>],
         only_track: Option<usize>,
+        recorder: &mut impl Recorder,
     ) -> HashMap<usize, HashSet<Complex<isize>>> {
         let mut results = HashMap::from_iter(
             self.knots
@@ -85,11 +114,12 @@ impl LongRope {
                     let d = self.knots[i - 1] - self.knots[i];
                     if d.norm_sqr() >= 4 {
                         self.knots[i] += sign(&d);
-                        if only_track.map_or(true, |o| o == i) {
+                        if only_track.is_none_or(|o| o == i) {
                             results.entry(i).or_default().insert(self.knots[i]);
                         }
                     }
                 }
+                recorder.record(|| self.draw(&results));
             }
         });
         results
@@ -101,11 +131,35 @@ pub fn part_1(moves: &[Complex<isize>]) -> usize {
     Rope::default().tail_positions(moves).len()
 }
 
+/// Part 1 as a rope of two knots, with the same code as part 2.
+pub fn part_1_long(moves: &[Complex<isize>]) -> usize {
+    LongRope::with_len(2).tail_positions(moves, Some(1), &mut Discard)[&1].len()
+}
+
 #[aoc(day9, part2)]
 pub fn part_2(moves: &[Complex<isize>]) -> usize {
-    LongRope::with_len(10).tail_positions(moves, Some(9))[&9].len()
+    LongRope::with_len(10).tail_positions(moves, Some(9), &mut Discard)[&9].len()
 }
 
+/// Part 2, one frame per step of the head.
+pub fn animate(moves: &[Complex<isize>], recorder: &mut impl Recorder) {
+    LongRope::with_len(10).tail_positions(moves, Some(9), recorder);
+}
+
+/// The head's motions, as vectors.
+pub struct Motions(pub Vec<Complex<isize>>);
+
+day! {
+    Motions.0, 9, usize, usize;
+    const ALTERNATES: &'static [Alternate<Self>] = &[Alternate {
+        part: 1,
+        name: "long-rope",
+        solve: |motions| part_1_long(&motions.0).into(),
+    }];
+}
+
+pub static SOLUTIONS: [runner::Day; 1] = [runner::Day::of::<Motions>()];
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -115,12 +169,13 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(&get_input(INPUT)), 13);
+        assert_eq!(part_1(&get_input(INPUT).unwrap()), 13);
+        assert_eq!(part_1_long(&get_input(INPUT).unwrap()), 13);
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(&get_input(INPUT)), 1);
-        assert_eq!(part_2(&get_input(INPUT_2)), 36);
+        assert_eq!(part_2(&get_input(INPUT).unwrap()), 1);
+        assert_eq!(part_2(&get_input(INPUT_2).unwrap()), 36);
     }
 }
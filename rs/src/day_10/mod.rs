@@ -1,12 +1,24 @@
+use crate::{error::ParseError, runner};
+#[cfg(not(feature = "nightly"))]
+use crate::{grid::Grid, render::Canvas};
+
+/// Each instruction becomes one 0 per cycle it takes, with the `addx` operand on its final cycle.
 #[aoc_generator(day10)]
-pub fn get_input(input: &str) -> Vec<isize> {
-    input
-        .lines()
-        .flat_map(|l| {
-            l.split_whitespace()
-                .map(|s| s.parse::<isize>().unwrap_or(0))
-        })
-        .collect()
+pub fn get_input(input: &str) -> Result<Vec<isize>, ParseError> {
+    let mut result = Vec::new();
+    for l in input.lines() {
+        if l == "noop" {
+            result.push(0);
+        } else if let Some(n) = l.strip_prefix("addx ") {
+            let n = n
+                .parse::<isize>()
+                .map_err(|_| ParseError::at(input, n, "integer").day(10))?;
+            result.extend([0, n]);
+        } else {
+            return Err(ParseError::at(input, l, "`noop` or `addx`").day(10));
+        }
+    }
+    Ok(result)
 }
 
 #[aoc(day10, part1)]
@@ -23,26 +35,47 @@ pub fn part_1(ns: &[isize]) -> isize {
         .0
 }
 
+/// The screen as rows of `█` and ` `, for reading the letters off of.
 #[aoc(day10, part2)]
-pub fn part_2(ns: &[isize]) -> &'static str {
-    let letters: String = (0..)
+pub fn part_2(ns: &[isize]) -> String {
+    let pixels = (0..)
         .zip(ns.iter())
         .fold((Vec::new(), 1), |(mut pixels, x), (i, n)| {
             pixels.push(i % 40 - 1 <= x && x <= i % 40 + 1);
             (pixels, x + n)
         })
-        .0
+        .0;
+    screen(pixels)
+}
+
+#[cfg(feature = "nightly")]
+fn screen(pixels: Vec<bool>) -> String {
+    pixels
         .iter()
         .map(|&pixel| if pixel { '█' } else { ' ' })
         .collect::<Vec<char>>()
         .chunks(40)
         .intersperse(&['\n'])
         .flatten()
-        .collect();
-    println!("{}", letters);
-    ""
+        .collect()
+}
+
+#[cfg(not(feature = "nightly"))]
+fn screen(pixels: Vec<bool>) -> String {
+    Canvas::from_grid(&Grid {
+        items: pixels,
+        w: 40,
+    })
+    .to_string()
 }
 
+/// The program as a change to `X` on each cycle, per [`get_input`].
+pub struct Program(pub Vec<isize>);
+
+day!(Program.0, 10, isize, String);
+
+pub static SOLUTIONS: [runner::Day; 1] = [runner::Day::of::<Program>()];
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -51,11 +84,20 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(&get_input(INPUT)), 13140);
+        assert_eq!(part_1(&get_input(INPUT).unwrap()), 13140);
     }
 
     #[test]
     fn test_part_2() {
-        // TODO
+        let screen = "##..##..##..##..##..##..##..##..##..##..\n\
+                      ###...###...###...###...###...###...###.\n\
+                      ####....####....####....####....####....\n\
+                      #####.....#####.....#####.....#####.....\n\
+                      ######......######......######......####\n\
+                      #######.......#######.......#######.....";
+        assert_eq!(
+            part_2(&get_input(INPUT).unwrap()),
+            screen.replace('#', "█").replace('.', " ")
+        );
     }
 }
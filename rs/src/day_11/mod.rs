@@ -11,10 +11,15 @@ use nom::{
 };
 use num::integer::lcm;
 
+use crate::{
+    error::{finish, ParseError},
+    runner,
+};
+
 // Monkey {usize}:
 //   Starting items: {str}
 //   Operation: new = old {op} {operand}
-//   Test: divisible by {usize}
+//   Test: divisible by {u64}
 //     If true: throw to monkey {usize}
 //     If false: throw to monkey {usize}
 
@@ -42,10 +47,10 @@ fn parse_monkey(s: &str) -> IResult<&str, Monkey> {
     )(s)
 }
 
-fn parse_items(s: &str) -> IResult<&str, Vec<usize>> {
+fn parse_items(s: &str) -> IResult<&str, Vec<u64>> {
     preceded(
         tag("Starting items: "),
-        separated_list1(tag(", "), map_res(digit1, |n: &str| n.parse::<usize>())),
+        separated_list1(tag(", "), map_res(digit1, |n: &str| n.parse::<u64>())),
     )(s)
 }
 
@@ -60,7 +65,7 @@ fn parse_operation(s: &str) -> IResult<&str, Operation> {
                 )),
                 alt((
                     value(Operand::Old, tag("old")),
-                    map_res(digit1, |n: &str| n.parse::<usize>().map(Operand::Num)),
+                    map_res(digit1, |n: &str| n.parse::<u64>().map(Operand::Num)),
                 )),
             )),
             |(operator, operand)| Operation {
@@ -72,10 +77,10 @@ fn parse_operation(s: &str) -> IResult<&str, Operation> {
     )(s)
 }
 
-fn parse_div(s: &str) -> IResult<&str, usize> {
+fn parse_div(s: &str) -> IResult<&str, u64> {
     preceded(
         tag("Test: divisible by "),
-        map_res(digit1, |n: &str| n.parse::<usize>()),
+        map_res(digit1, |n: &str| n.parse::<u64>()),
     )(s)
 }
 
@@ -100,13 +105,13 @@ enum Operator {
 }
 
 impl TryFrom<&str> for Operator {
-    type Error = String;
+    type Error = ParseError;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
         match s {
             "+" => Ok(Operator::Plus),
             "*" => Ok(Operator::Times),
-            _ => Err(format!("invalid operator: {}", s)),
+            _ => Err(ParseError::at(s, s, "`+` or `*`").day(11)),
         }
     }
 }
@@ -114,7 +119,7 @@ impl TryFrom<&str> for Operator {
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 enum Operand {
     Old,
-    Num(usize),
+    Num(u64),
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -125,7 +130,7 @@ struct Operation {
 }
 
 impl Operation {
-    fn compute(&self, old: usize) -> usize {
+    fn compute(&self, old: u64) -> u64 {
         let a = match self.arg_a {
             Operand::Old => old,
             Operand::Num(n) => n,
@@ -143,22 +148,24 @@ impl Operation {
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Monkey {
-    items: Vec<usize>,
+    /// Worry levels get multiplied together, so these are kept as `u64` even where `usize` is
+    /// only 32 bits.
+    items: Vec<u64>,
     operation: Operation,
-    test_div: usize,
+    test_div: u64,
     dst_true: usize,
     dst_false: usize,
 }
 
 impl TryFrom<&str> for Monkey {
-    type Error = String;
+    type Error = ParseError;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
-        parse_monkey(s).map(|(_, m)| m).map_err(|e| e.to_string())
+        finish(s, parse_monkey(s)).map_err(|e| e.day(11))
     }
 }
 
-fn do_rounds(monkeys: &mut Vec<Rc<RefCell<Monkey>>>, n: usize, worry_div: usize) -> Vec<usize> {
+fn do_rounds(monkeys: &mut [Rc<RefCell<Monkey>>], n: usize, worry_div: u64) -> Vec<u64> {
     let mut results = vec![0; monkeys.len()];
     let l = monkeys.iter().map(|m| m.borrow().test_div).fold(1, lcm);
     for _ in 0..n {
@@ -166,8 +173,8 @@ fn do_rounds(monkeys: &mut Vec<Rc<RefCell<Monkey>>>, n: usize, worry_div: usize)
             let mut m = monkeys[i].borrow_mut();
             while let Some(old) = m.items.pop() {
                 results[i] += 1;
-                let new = ((m.operation.compute(old) % l) / worry_div) as usize;
-                if new % m.test_div == 0 {
+                let new = (m.operation.compute(old) % l) / worry_div;
+                if new.is_multiple_of(m.test_div) {
                     monkeys[m.dst_true].borrow_mut().items.push(new);
                 } else {
                     monkeys[m.dst_false].borrow_mut().items.push(new);
@@ -179,37 +186,66 @@ fn do_rounds(monkeys: &mut Vec<Rc<RefCell<Monkey>>>, n: usize, worry_div: usize)
 }
 
 #[aoc_generator(day11)]
-pub fn get_input(input: &str) -> Vec<Rc<RefCell<Monkey>>> {
-    parse_monkeys(input)
-        .unwrap()
-        .1
+pub fn get_input(input: &str) -> Result<Vec<Rc<RefCell<Monkey>>>, ParseError> {
+    let monkeys = finish(input, parse_monkeys(input)).map_err(|e| e.day(11))?;
+    // having parsed, every target is the number right after one of these
+    let throw = "throw to monkey ";
+    for (i, _) in input.match_indices(throw) {
+        let target = &input[i + throw.len()..];
+        let digits = target
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(target.len());
+        if target[..digits]
+            .parse()
+            .is_ok_and(|n: usize| n >= monkeys.len())
+        {
+            let expected = format!("a monkey up to {}", monkeys.len() - 1);
+            return Err(ParseError::at(input, target, expected).day(11));
+        }
+    }
+    Ok(monkeys
         .into_iter()
         .map(|m| Rc::new(RefCell::new(m)))
-        .collect()
+        .collect())
 }
 
-fn monkey_business(monkeys: &mut [Rc<RefCell<Monkey>>], r: usize, w: usize) -> usize {
+fn monkey_business(monkeys: &mut [Rc<RefCell<Monkey>>], r: usize, w: u64) -> u64 {
     let mut inspections = do_rounds(&mut monkeys.to_owned(), r, w);
     inspections.sort();
     inspections.pop().unwrap() * inspections.pop().unwrap()
 }
 
 #[aoc(day11, part1)]
-pub fn part_1(monkeys: &[Rc<RefCell<Monkey>>]) -> usize {
+pub fn part_1(monkeys: &[Rc<RefCell<Monkey>>]) -> u64 {
     monkey_business(&mut monkeys.to_owned(), 20, 3)
 }
 
 #[aoc(day11, part2)]
-pub fn part_2(monkeys: &[Rc<RefCell<Monkey>>]) -> usize {
+pub fn part_2(monkeys: &[Rc<RefCell<Monkey>>]) -> u64 {
     monkey_business(&mut monkeys.to_owned(), 10_000, 1)
 }
 
+/// The monkeys, in order, as they start out.
+pub struct Monkeys(pub Vec<Rc<RefCell<Monkey>>>);
+
+day!(Monkeys.0, 11, u64, u64);
+
+pub static SOLUTIONS: [runner::Day; 1] = [runner::Day::of::<Monkeys>()];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     const INPUT: &str = include_str!("../../test_data/day_11.txt");
 
+    #[test]
+    fn test_unknown_monkey() {
+        let input = INPUT.replacen("throw to monkey 0", "throw to monkey 4", 1);
+        let err = get_input(&input).err().unwrap();
+        assert_eq!((err.day, err.line, err.column), (Some(11), 13, 31));
+        assert_eq!(err.expected, "a monkey up to 3");
+    }
+
     #[test]
     fn test_parse_monkey() {
         let s = concat!(
@@ -241,11 +277,11 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(&get_input(INPUT)), 10605);
+        assert_eq!(part_1(&get_input(INPUT).unwrap()), 10605);
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(&get_input(INPUT)), 2713310158);
+        assert_eq!(part_2(&get_input(INPUT).unwrap()), 2713310158);
     }
 }
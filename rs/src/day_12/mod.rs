@@ -1,90 +1,80 @@
-use std::{
-    cmp::Ordering,
-    collections::{BinaryHeap, HashMap},
+use crate::{
+    error::ParseError,
+    grid::Grid,
+    render::{Canvas, RED},
+    runner,
+    search::{self, Path, Space},
 };
 
-use crate::grid::Grid;
+/// Walking downhill from the end, which can drop at most one step at a time. Searching backwards
+/// like this lets part 2 stop at whichever `a` is closest.
+struct Descent<'a>(&'a Grid<u8>);
 
-#[derive(Debug, Eq, PartialEq)]
-struct HeapItem {
-    i: usize, // grid index
-    p: usize, // priority
-    v: u8,    // value
-}
+impl Space for Descent<'_> {
+    type State = usize;
 
-impl PartialOrd for HeapItem {
-    fn partial_cmp(&self, other: &HeapItem) -> Option<Ordering> {
-        // backwards since we want a min heap
-        other.p.partial_cmp(&self.p)
+    fn successors(&self, &i: &usize) -> Vec<(usize, usize)> {
+        self.0
+            .neighbors(i)
+            .filter(|&(_, v)| v + 1 >= self.0[i])
+            .map(|(j, _)| (j, 1))
+            .collect()
     }
 }
 
-impl Ord for HeapItem {
-    fn cmp(&self, other: &HeapItem) -> Ordering {
-        self.partial_cmp(other).unwrap()
-    }
-}
-
-fn hike(grid: &Grid<u8>, any_start: bool) -> usize {
+/// The shortest route up, as indices from the end back to the start.
+fn hike(grid: &Grid<u8>, any_start: bool) -> Path<usize> {
     let mut grid = grid.clone();
-    let mut origin_opt: Option<usize> = None;
-    let mut destination_opt: Option<usize> = None;
-    let mut q: BinaryHeap<HeapItem> = BinaryHeap::new();
-    let mut distance: HashMap<usize, Option<usize>> = HashMap::new();
-    for (i, &v) in grid.items.iter().enumerate() {
-        if v == b'E' {
-            q.push(HeapItem {
-                i,
-                p: usize::MAX,
-                v: b'z',
-            });
-            origin_opt = Some(i);
-            distance.insert(i, Some(0));
-        } else {
-            distance.insert(i, None);
-        }
-        if v == b'S' {
-            destination_opt = Some(i);
-        }
-    }
-    let origin = origin_opt.unwrap_or_else(|| panic!("no end found"));
-    let destination = destination_opt.unwrap_or_else(|| panic!("no start found"));
+    let position = |marker: u8| grid.items.iter().position(|&v| v == marker);
+    let origin = position(b'E').unwrap_or_else(|| panic!("no end found"));
+    let destination = position(b'S').unwrap_or_else(|| panic!("no start found"));
     grid[destination] = b'a';
     grid[origin] = b'z';
-    while let Some(item) = q.pop() {
-        for (j, v) in grid
-            .neighbors(item.i)
-            .into_iter()
-            .filter(|(_, v)| *v + 1 >= item.v)
-        {
-            let d_new = distance[&item.i].unwrap() + 1;
-            if (any_start && v == b'a') || (!any_start && j == destination) {
-                return d_new;
-            }
-            if distance[&j].map_or(true, |d| d > d_new) {
-                distance.insert(j, Some(d_new));
-                q.push(HeapItem { i: j, p: d_new, v });
-            }
-        }
-    }
-    distance[&destination].unwrap()
+    let is_start = |&i: &usize| i == destination || (any_start && grid[i] == b'a');
+    search::bfs(&Descent(&grid), [origin], is_start).expect("no way up")
+}
+
+/// The heightmap with the shortest route up drawn over it, from `S` or, with `any_start`,
+/// whichever `a` is closest.
+pub fn draw(grid: &Grid<u8>, any_start: bool) -> Canvas {
+    let route = hike(grid, any_start).states.into_iter().rev().map(|i| {
+        let (x, y) = grid.i_to_xy(i);
+        (x as isize, y as isize)
+    });
+    let mut canvas = Canvas::from_grid(grid);
+    canvas.path(route, RED);
+    canvas
 }
 
 #[aoc_generator(day12)]
-pub fn get_input(input: &str) -> Grid<u8> {
-    Grid::try_from(input).unwrap()
+pub fn get_input(input: &str) -> Result<Grid<u8>, ParseError> {
+    let grid = Grid::try_from(input).map_err(|e| e.day(12))?;
+    for marker in ['S', 'E'] {
+        if !grid.items.contains(&(marker as u8)) {
+            let expected = format!("a `{}` in the heightmap", marker);
+            return Err(ParseError::at_end(input, expected).day(12));
+        }
+    }
+    Ok(grid)
 }
 
 #[aoc(day12, part1)]
 pub fn part_1(grid: &Grid<u8>) -> usize {
-    hike(grid, false)
+    hike(grid, false).cost
 }
 
 #[aoc(day12, part2)]
 pub fn part_2(grid: &Grid<u8>) -> usize {
-    hike(grid, true)
+    hike(grid, true).cost
 }
 
+/// The heightmap, with `S` and `E` as per [`get_input`].
+pub struct Heightmap(pub Grid<u8>);
+
+day!(Heightmap.0, 12, usize, usize);
+
+pub static SOLUTIONS: [runner::Day; 1] = [runner::Day::of::<Heightmap>()];
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -93,11 +83,21 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(&get_input(INPUT)), 31);
+        assert_eq!(part_1(&get_input(INPUT).unwrap()), 31);
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(&get_input(INPUT)), 29);
+        assert_eq!(part_2(&get_input(INPUT).unwrap()), 29);
+    }
+
+    #[test]
+    fn test_draw() {
+        let text = draw(&get_input(INPUT).unwrap(), false).to_string();
+        // a step per move, from the `S` that the first arrow covers to the `*` over `E`
+        let arrows = text.chars().filter(|c| "↑↓←→".contains(*c)).count();
+        assert_eq!(arrows, 31);
+        assert!(!text.contains('S') && !text.contains('E'));
+        assert_eq!(text.lines().nth(2).unwrap().chars().nth(5), Some('*'));
     }
 }
@@ -1,4 +1,4 @@
-use std::{collections::VecDeque, iter::once};
+use std::{cmp::Ordering, collections::VecDeque, str::FromStr};
 
 use nom::{
     branch::alt,
@@ -10,7 +10,12 @@ use nom::{
     IResult,
 };
 
-#[derive(Clone, Eq, PartialEq)]
+use crate::{
+    error::{finish, ParseError},
+    runner,
+};
+
+#[derive(Clone)]
 pub enum Packet {
     Int(usize),
     List(VecDeque<Packet>),
@@ -43,31 +48,46 @@ impl std::fmt::Display for Packet {
     }
 }
 
-impl PartialOrd for Packet {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
         match (self, other) {
-            (Packet::Int(a), Packet::Int(b)) => a.partial_cmp(b),
-            (Packet::Int(_), Packet::List(_)) => {
-                Packet::List(vec![self.clone()].into()).partial_cmp(other)
-            }
+            (Packet::Int(a), Packet::Int(b)) => a.cmp(b),
+            (Packet::Int(_), Packet::List(_)) => Packet::List(vec![self.clone()].into()).cmp(other),
             (Packet::List(_), Packet::Int(_)) => {
-                self.partial_cmp(&Packet::List(vec![other.clone()].into()))
+                self.cmp(&Packet::List(vec![other.clone()].into()))
             }
             (Packet::List(a), Packet::List(b)) => {
                 for (aa, bb) in a.iter().zip(b.iter()) {
-                    if aa.partial_cmp(bb) != Some(std::cmp::Ordering::Equal) {
-                        return aa.partial_cmp(bb);
+                    if aa.cmp(bb) != Ordering::Equal {
+                        return aa.cmp(bb);
                     }
                 }
-                a.len().partial_cmp(&b.len())
+                a.len().cmp(&b.len())
             }
         }
     }
 }
 
-impl Ord for Packet {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.partial_cmp(other).unwrap()
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Packets are equal when they're in order both ways round, so e.g. `1` and `[[1]]` are equal.
+impl PartialEq for Packet {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Packet {}
+
+impl FromStr for Packet {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        finish(s, parse_packet(s)).map_err(|e| e.day(13))
     }
 }
 
@@ -100,8 +120,8 @@ fn parse_packet(s: &str) -> IResult<&str, Packet> {
 }
 
 #[aoc_generator(day13)]
-pub fn get_input(input: &str) -> Vec<(Packet, Packet)> {
-    parse_all_packets(input).unwrap().1
+pub fn get_input(input: &str) -> Result<Vec<(Packet, Packet)>, ParseError> {
+    finish(input, parse_all_packets(input)).map_err(|e| e.day(13))
 }
 
 #[aoc(day13, part1)]
@@ -119,20 +139,34 @@ pub fn part_1(packets: &[(Packet, Packet)]) -> usize {
 #[aoc(day13, part2)]
 pub fn part_2(packets: &[(Packet, Packet)]) -> usize {
     let divider = |n: usize| Packet::List(vec![Packet::List(vec![Packet::Int(n)].into())].into());
-    let mut packets: Vec<Packet> = packets
+    let dividers = [divider(2), divider(6)];
+    // tagged rather than found by value afterwards, since e.g. `[2]` is equal to `[[2]]`
+    let mut packets: Vec<(&Packet, bool)> = packets
         .iter()
-        .cloned()
-        .chain(once((divider(2), divider(6))))
-        .flat_map(|(p1, p2)| once(p1).chain(once(p2)))
+        .flat_map(|(p1, p2)| [(p1, false), (p2, false)])
+        .chain(dividers.iter().map(|d| (d, true)))
         .collect();
-    packets.sort();
-    let d1 = packets.iter().position(|p| p == &divider(2)).unwrap() + 1;
-    let d2 = packets.iter().position(|p| p == &divider(6)).unwrap() + 1;
-    d1 * d2
+    // stable, so the dividers go after any packets equal to them
+    packets.sort_by(|a, b| a.0.cmp(b.0));
+    packets
+        .iter()
+        .enumerate()
+        .filter(|(_, &(_, is_divider))| is_divider)
+        .map(|(i, _)| i + 1)
+        .product()
 }
 
+/// The packets, in their pairs.
+pub struct PacketPairs(pub Vec<(Packet, Packet)>);
+
+day!(PacketPairs.0, 13, usize, usize);
+
+pub static SOLUTIONS: [runner::Day; 1] = [runner::Day::of::<PacketPairs>()];
+
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     const INPUT: &str = include_str!("../../test_data/day_13.txt");
@@ -170,11 +204,43 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(&get_input(INPUT)), 13);
+        assert_eq!(part_1(&get_input(INPUT).unwrap()), 13);
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(&get_input(INPUT)), 140);
+        assert_eq!(part_2(&get_input(INPUT).unwrap()), 140);
+        // `[2]` sorts level with the `[[2]]` divider, which goes after it
+        assert_eq!(part_2(&get_input("[1]\n[2]\n\n[3]\n[4]").unwrap()), 18);
+    }
+
+    fn packet() -> impl Strategy<Value = Packet> {
+        (0..=10usize)
+            .prop_map(Packet::Int)
+            .prop_recursive(4, 32, 5, |inner| {
+                prop::collection::vec_deque(inner, 0..5).prop_map(Packet::List)
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn test_total_order(a in packet(), b in packet(), c in packet()) {
+            prop_assert_eq!(a.cmp(&a), Ordering::Equal);
+            prop_assert_eq!(a.cmp(&b), b.cmp(&a).reverse());
+            prop_assert_eq!(a == b, a.cmp(&b) == Ordering::Equal);
+            if a <= b && b <= c {
+                prop_assert!(a <= c);
+            }
+            let mut sorted = [a, b, c];
+            sorted.sort();
+            prop_assert!(sorted[0] <= sorted[1] && sorted[1] <= sorted[2]);
+        }
+
+        #[test]
+        fn test_display_round_trip(p in packet()) {
+            let s = p.to_string();
+            let parsed = finish(&s, parse_packet(&s)).unwrap();
+            prop_assert_eq!(format!("{:?}", parsed), format!("{:?}", p));
+        }
     }
 }
@@ -11,6 +11,13 @@ use nom::{
 };
 use num::Complex;
 
+use crate::{
+    error::{finish, ParseError},
+    record::{Discard, Recorder},
+    render::Canvas,
+    runner,
+};
+
 fn parse_scans(s: &str) -> IResult<&str, Vec<Vec<Complex<usize>>>> {
     separated_list1(
         line_ending,
@@ -43,10 +50,27 @@ fn scans_to_coords(scans: Vec<Vec<Complex<usize>>>) -> HashSet<Complex<usize>> {
     )
 }
 
+/// Rocks as `#`, resting sand as `o` and the source as `+`.
+fn draw(
+    start: Complex<usize>,
+    rocks: &HashSet<Complex<usize>>,
+    sand: &HashSet<Complex<usize>>,
+) -> Canvas {
+    let xy = |c: &Complex<usize>| (c.re as isize, c.im as isize);
+    let mut canvas =
+        Canvas::from_points(rocks.iter().chain(sand).chain([&start]).map(xy), '.', '.');
+    canvas
+        .highlight(rocks.iter().map(xy), '#')
+        .highlight(sand.iter().map(xy), 'o')
+        .highlight([xy(&start)], '+');
+    canvas
+}
+
 fn sand_rests_at(
     start: Complex<usize>,
     rocks: &HashSet<Complex<usize>>,
     floor: bool,
+    recorder: &mut impl Recorder,
 ) -> HashSet<Complex<usize>> {
     let bottom = rocks.iter().map(|c| c.im).max().unwrap();
     let mut sand = HashSet::new();
@@ -71,6 +95,7 @@ fn sand_rests_at(
             }
             if blocked {
                 sand.insert(current);
+                recorder.record(|| draw(start, rocks, &sand));
                 break;
             }
         }
@@ -82,33 +107,58 @@ fn sand_rests_at(
 }
 
 #[aoc_generator(day14)]
-pub fn get_input(input: &str) -> HashSet<Complex<usize>> {
-    scans_to_coords(parse_scans(input).unwrap().1)
+pub fn get_input(input: &str) -> Result<HashSet<Complex<usize>>, ParseError> {
+    finish(input, parse_scans(input))
+        .map(scans_to_coords)
+        .map_err(|e| e.day(14))
 }
 
 #[aoc(day14, part1)]
 pub fn part_1(rocks: &HashSet<Complex<usize>>) -> usize {
-    sand_rests_at(Complex::new(500, 0), rocks, false).len()
+    sand_rests_at(Complex::new(500, 0), rocks, false, &mut Discard).len()
 }
 
 #[aoc(day14, part2)]
 pub fn part_2(rocks: &HashSet<Complex<usize>>) -> usize {
-    sand_rests_at(Complex::new(500, 0), rocks, true).len()
+    sand_rests_at(Complex::new(500, 0), rocks, true, &mut Discard).len()
+}
+
+/// Part 2, one frame per grain of sand coming to rest.
+pub fn animate(rocks: &HashSet<Complex<usize>>, recorder: &mut impl Recorder) {
+    sand_rests_at(Complex::new(500, 0), rocks, true, recorder);
 }
 
+/// Every point of rock in the cave.
+pub struct Cave(pub HashSet<Complex<usize>>);
+
+day!(Cave.0, 14, usize, usize);
+
+pub static SOLUTIONS: [runner::Day; 1] = [runner::Day::of::<Cave>()];
+
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
+    use crate::inputgen;
 
     const INPUT: &str = "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9";
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(&get_input(INPUT)), 24);
+        assert_eq!(part_1(&get_input(INPUT).unwrap()), 24);
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(&get_input(INPUT)), 93);
+        assert_eq!(part_2(&get_input(INPUT).unwrap()), 93);
+    }
+
+    proptest! {
+        #[test]
+        fn test_floor_holds_more(input in inputgen::arbitrary(14, 1..=10)) {
+            let rocks = get_input(&input).unwrap();
+            prop_assert!(part_2(&rocks) >= part_1(&rocks));
+        }
     }
 }
@@ -1,15 +1,18 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::HashMap;
 
 use itertools::Itertools;
 use num::Complex;
 use sscanf::scanf;
 
+use crate::{error::ParseError, interval::IntervalSet, runner};
+
 pub struct Area {
     pairs: Vec<(Complex<isize>, Complex<isize>, isize)>,
 }
 
 impl Area {
-    fn contains(&self, point: Complex<isize>) -> bool {
+    /// Whether `point` is within range of a sensor, so that it can't have an undetected beacon.
+    pub fn contains(&self, point: Complex<isize>) -> bool {
         self.pairs
             .iter()
             .any(|(s, _, r)| (s - point).l1_norm() <= *r)
@@ -17,7 +20,7 @@ impl Area {
 }
 
 impl TryFrom<&str> for Area {
-    type Error = String;
+    type Error = ParseError;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
         let pairs = s
@@ -27,7 +30,14 @@ impl TryFrom<&str> for Area {
                     l,
                     "Sensor at x={isize}, y={isize}: closest beacon is at x={isize}, y={isize}"
                 )
-                .map_err(|_| format!("couldn't parse line: {}", l))?;
+                .map_err(|_| {
+                    ParseError::at(
+                        s,
+                        l,
+                        "`Sensor at x=.., y=..: closest beacon is at x=.., y=..`",
+                    )
+                    .day(15)
+                })?;
                 let s = complex!(sx, sy);
                 let b = complex!(bx, by);
                 Ok((s, b, (s - b).l1_norm()))
@@ -37,32 +47,35 @@ impl TryFrom<&str> for Area {
     }
 }
 
+/// The positions on row `y` within range of a sensor. Ranges are closed, since a sensor covers
+/// the points at exactly its beacon's distance too.
+fn coverage(area: &Area, y: isize) -> IntervalSet<isize> {
+    area.pairs
+        .iter()
+        .filter_map(|(s, _, r)| {
+            let dx = r - (y - s.im).abs();
+            (dx >= 0).then(|| s.re - dx..=s.re + dx)
+        })
+        .collect()
+}
+
+/// How many positions on row `y` can't hold a beacon: those within range of a sensor, except for
+/// beacons that are already known to be there.
 fn line_overlap(area: &Area, y: isize) -> usize {
-    let mut overlaps: VecDeque<(isize, isize)> = VecDeque::new();
-    for (s, _, r) in area.pairs.iter() {
-        let dy = (y - s.im).abs();
-        if dy <= *r {
-            overlaps.push_back((s.re - (r - dy), s.re + (r - dy)));
-        }
-    }
-    overlaps.make_contiguous().sort();
-    let mut current = overlaps.pop_front().unwrap();
-    let mut result = Vec::new();
-    for o in overlaps.iter() {
-        if o.0 > current.1 {
-            result.push(current);
-            current = *o;
-        } else if o.1 > current.1 {
-            current.1 = o.1;
-        }
-    }
-    result.push(current);
-    result.iter().map(|(a, b)| (b - a) as usize).sum()
+    let covered = coverage(area, y);
+    let beacons = area
+        .pairs
+        .iter()
+        .filter(|(_, b, _)| b.im == y && covered.contains(b.re))
+        .map(|(_, b, _)| b.re)
+        .unique()
+        .count();
+    covered.len() as usize - beacons
 }
 
 #[aoc_generator(day15)]
-pub fn get_input(input: &str) -> Area {
-    input.try_into().unwrap()
+pub fn get_input(input: &str) -> Result<Area, ParseError> {
+    input.try_into()
 }
 
 #[aoc(day15, part1)]
@@ -96,15 +109,19 @@ fn sole_beacon(area: &Area, limits: ((isize, isize), (isize, isize))) -> Option<
         })
 }
 
-fn frequency(beacon: (isize, isize)) -> usize {
-    (beacon.0 * 4_000_000isize + beacon.1) as usize
+fn frequency(beacon: (isize, isize)) -> u64 {
+    beacon.0 as u64 * 4_000_000 + beacon.1 as u64
 }
 
 #[aoc(day15, part2)]
-pub fn part_2(area: &Area) -> usize {
+pub fn part_2(area: &Area) -> u64 {
     frequency(sole_beacon(area, ((0, 0), (4_000_000, 4_000_000))).unwrap())
 }
 
+day!(Area, 15, usize, u64);
+
+pub static SOLUTIONS: [runner::Day; 1] = [runner::Day::of::<Area>()];
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -113,13 +130,25 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        assert_eq!(line_overlap(&get_input(INPUT), 10), 26);
+        assert_eq!(line_overlap(&get_input(INPUT).unwrap(), 10), 26);
+    }
+
+    #[test]
+    fn test_coverage() {
+        let area = get_input(INPUT).unwrap();
+        // -2 to 24 is 27 positions, one of them the beacon at 2,10
+        assert_eq!(
+            coverage(&area, 10).iter_closed().collect::<Vec<_>>(),
+            vec![-2..=24]
+        );
+        // no beacons on row 11, so nothing to take away
+        assert_eq!(line_overlap(&area, 11), coverage(&area, 11).len() as usize);
     }
 
     #[test]
     fn test_part_2() {
         assert_eq!(
-            frequency(sole_beacon(&get_input(INPUT), ((0, 0), (20, 20))).unwrap()),
+            frequency(sole_beacon(&get_input(INPUT).unwrap(), ((0, 0), (20, 20))).unwrap()),
             56000011
         );
     }
@@ -1,7 +1,4 @@
-use std::{
-    collections::{HashMap, HashSet},
-    num::ParseIntError,
-};
+use std::{collections::HashMap, num::ParseIntError};
 
 use itertools::Itertools;
 use nom::{
@@ -15,6 +12,12 @@ use nom::{
 };
 use petgraph::{algo::floyd_warshall, prelude::DiGraphMap};
 
+use crate::{
+    error::{finish, ParseError},
+    runner,
+    solution::Alternate,
+};
+
 pub struct Valves {
     distances: HashMap<usize, HashMap<usize, usize>>,
     rates: HashMap<usize, usize>,
@@ -30,28 +33,42 @@ impl Valves {
         Self { distances, rates }
     }
 
+    /// The most pressure that can be released in `minutes`, starting at the valve named `start`.
+    pub fn most_pressure(&self, start: &str, minutes: usize) -> usize {
+        self.release(valve_name_to_key(&start), minutes)
+    }
+
+    /// As [`Valves::most_pressure`], but with an elephant's help after spending 4 of the minutes
+    /// teaching it.
+    pub fn most_pressure_with_elephant(&self, start: &str, minutes: usize) -> usize {
+        self.elephant(valve_name_to_key(&start), minutes)
+    }
+
     fn release(&self, start: usize, t_0: usize) -> usize {
+        trace_span!("release", minutes = t_0);
         let mut pressure = 0;
         let nonzero_ix: HashMap<usize, usize> = self
             .rates
             .iter()
+            .filter(|(_, &rate)| rate > 0)
             .enumerate()
-            .filter_map(|(i, (&valve, &rate))| (rate > 0).then_some((valve, i)))
+            .map(|(i, (&valve, _))| (valve, i))
             .collect();
         if nonzero_ix.len() > usize::BITS as usize {
             panic!("too many nonzero valves for bitmask");
         }
         let mut stack: Vec<(usize, usize, usize, usize)> = vec![(t_0, 0, start, 0)];
         while let Some((t, released, valve, visited_mask)) = stack.pop() {
+            stat!("day_16.release.stack", stack.len());
             pressure = pressure.max(released);
             stack.extend(
                 self.distances[&valve]
                     .iter()
                     .filter(|(valve, &dist)| {
-                        dist < t - 2
+                        dist + 2 < t
                             && nonzero_ix
                                 .get(valve)
-                                .map_or(false, |i| visited_mask & 1 << i == 0)
+                                .is_some_and(|i| visited_mask & 1 << i == 0)
                     })
                     .map(|(valve, &dist)| {
                         (
@@ -66,21 +83,30 @@ impl Valves {
         pressure
     }
 
-    fn elephant(&self, start: usize, t_0: usize) -> usize {
-        // Map from bitmask of opened valves to total released pressure.
+    /// As [`Valves::most_pressure`], but by way of the same search as part 2's, which finds the
+    /// best for every set of valves that can be opened rather than just the best overall.
+    pub fn most_pressure_by_subset(&self, start: &str, minutes: usize) -> usize {
+        let pressures = self.by_subset(valve_name_to_key(&start), minutes);
+        pressures.into_values().max().unwrap_or(0)
+    }
+
+    /// Map from bitmask of opened valves to the most pressure released by opening just those.
+    fn by_subset(&self, start: usize, t_0: usize) -> HashMap<usize, usize> {
+        trace_span!("by_subset", minutes = t_0);
         let mut pressures: HashMap<usize, usize> = HashMap::new();
         let nonzero_ix: HashMap<usize, usize> = self
             .rates
             .iter()
+            .filter(|(_, &rate)| rate > 0)
             .enumerate()
-            .filter_map(|(i, (&valve, &rate))| (rate > 0).then_some((valve, i)))
+            .map(|(i, (&valve, _))| (valve, i))
             .collect();
         if nonzero_ix.len() > usize::BITS as usize {
             panic!("too many nonzero valves for bitmask");
         }
-        let mut stack: Vec<(usize, usize, usize, usize, HashSet<usize>)> =
-            vec![(t_0 - 4, 0, start, 0, HashSet::new())];
-        while let Some((t, released, valve, visited_mask, masks)) = stack.pop() {
+        let mut stack: Vec<(usize, usize, usize, usize)> = vec![(t_0, 0, start, 0)];
+        while let Some((t, released, valve, visited_mask)) = stack.pop() {
+            stat!("day_16.by_subset.stack", stack.len());
             pressures
                 .entry(visited_mask)
                 .and_modify(|p| *p = (*p).max(released))
@@ -89,26 +115,31 @@ impl Valves {
                 self.distances[&valve]
                     .iter()
                     .filter(|(valve, &dist)| {
-                        dist < t - 2
+                        dist + 2 < t
                             && nonzero_ix
                                 .get(valve)
-                                .map_or(false, |i| visited_mask & 1 << i == 0)
+                                .is_some_and(|i| visited_mask & 1 << i == 0)
                     })
                     .map(|(valve, &dist)| {
-                        let new_mask = visited_mask | 1 << nonzero_ix[valve];
                         (
                             t - dist - 1,
                             released + self.rates[valve] * (t - dist - 1),
                             *valve,
-                            new_mask,
-                            masks.union(&[new_mask].into()).copied().collect(),
+                            visited_mask | 1 << nonzero_ix[valve],
                         )
                     }),
             );
         }
+        pressures
+    }
+
+    fn elephant(&self, start: usize, t_0: usize) -> usize {
+        trace_span!("elephant", minutes = t_0);
+        let pressures = self.by_subset(start, t_0.saturating_sub(4));
+        stat!("day_16.elephant.subsets", pressures.len());
         pressures
             .iter()
-            .cartesian_product(pressures.clone().iter())
+            .cartesian_product(pressures.iter())
             .filter_map(|((m_a, r_a), (m_b, r_b))| (m_a ^ m_b == m_a + m_b).then_some(r_a + r_b))
             .max()
             .unwrap()
@@ -160,20 +191,31 @@ fn parse_valve(s: &str) -> IResult<&str, (usize, usize, Vec<usize>)> {
 }
 
 #[aoc_generator(day16)]
-pub fn get_input(input: &str) -> Valves {
-    parse_valves(input).unwrap().1
+pub fn get_input(input: &str) -> Result<Valves, ParseError> {
+    finish(input, parse_valves(input)).map_err(|e| e.day(16))
 }
 
 #[aoc(day16, part1)]
 pub fn part_1(valves: &Valves) -> usize {
-    valves.release(valve_name_to_key(&"AA"), 30)
+    valves.most_pressure("AA", 30)
 }
 
 #[aoc(day16, part2)]
 pub fn part_2(valves: &Valves) -> usize {
-    valves.elephant(valve_name_to_key(&"AA"), 30)
+    valves.most_pressure_with_elephant("AA", 30)
 }
 
+day! {
+    Valves, 16, usize, usize;
+    const ALTERNATES: &'static [Alternate<Self>] = &[Alternate {
+        part: 1,
+        name: "by-subset",
+        solve: |valves| valves.most_pressure_by_subset("AA", 30).into(),
+    }];
+}
+
+pub static SOLUTIONS: [runner::Day; 1] = [runner::Day::of::<Valves>()];
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -182,11 +224,13 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(&get_input(INPUT)), 1651);
+        assert_eq!(part_1(&get_input(INPUT).unwrap()), 1651);
+        let valves = get_input(INPUT).unwrap();
+        assert_eq!(valves.most_pressure_by_subset("AA", 30), 1651);
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(&get_input(INPUT)), 1707);
+        assert_eq!(part_2(&get_input(INPUT).unwrap()), 1707);
     }
 }
@@ -1,5 +1,14 @@
 use std::collections::HashMap;
 
+use crate::{
+    error::ParseError,
+    grid::Grid,
+    record::{Discard, Recorder},
+    render::Canvas,
+    runner::{self, Answer},
+    solution::Solution,
+};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Move {
     L,
@@ -62,7 +71,7 @@ impl Piece {
     /// Thus, to figure out if the piece should stop, we check the intersection of the piece's
     /// 4-row mask with the 1-to-4-row slice of the cave starting at `y` from the "top" (end of the
     /// cave vector).
-    fn stops_on(&self, x: usize, y: usize, cave: &Vec<u8>) -> bool {
+    fn stops_on(&self, x: usize, y: usize, cave: &[u8]) -> bool {
         let h = cave.len();
         y == h
             || self
@@ -73,16 +82,43 @@ impl Piece {
     }
 }
 
-struct Tetris {
+/// How many rows from the top of the tower to draw.
+const VIEW: usize = 24;
+
+/// The deepest [`Tetris::surface`] goes. Jets that never fill some column leave a shaft all the
+/// way down, and a surface following it would grow with the tower and never repeat.
+const MAX_SURFACE: usize = 64;
+
+/// The tower, as a bitmask per row from the floor up.
+#[derive(Default)]
+pub struct Tetris {
     rows: Vec<u8>,
 }
 
 impl Tetris {
-    fn new() -> Self {
+    pub fn new() -> Self {
         Self { rows: vec![] }
     }
 
-    fn do_moves(&mut self, moves: &[Move], n_rocks: usize) -> usize {
+    /// The top `VIEW` rows of the tower, with rock as `#`.
+    fn draw(&self) -> Canvas {
+        let items = self
+            .rows
+            .iter()
+            .rev()
+            .take(VIEW)
+            .flat_map(|row| (0..7).map(move |x| if row & 1 << x != 0 { '#' } else { '.' }))
+            .collect();
+        Canvas::from_grid(&Grid { items, w: 7 })
+    }
+
+    /// Drop `n_rocks` more rocks, pushed by the jets in `moves`, and give the tower's height.
+    pub fn do_moves(
+        &mut self,
+        moves: &[Move],
+        n_rocks: usize,
+        recorder: &mut impl Recorder,
+    ) -> usize {
         let ms = &mut moves.iter().cycle();
         for p in PIECES.iter().cycle().take(n_rocks) {
             let l = self.rows.len();
@@ -124,11 +160,39 @@ impl Tetris {
                     self.rows.push(mask[i + to_update]);
                 }
             }
+            recorder.record(|| self.draw());
         }
         self.rows.len()
     }
 
-    fn do_moves_but_smarter(&mut self, moves: &[Move], n_rocks: usize) -> usize {
+    /// The rows from the top down to the lowest one a falling rock could still reach, which is
+    /// all that matters for where the rocks after it land. A fixed number of rows isn't enough,
+    /// since with the right jets a rock can slide down a long shaft, but past `MAX_SURFACE` rows
+    /// it makes do with those.
+    fn surface(&self) -> Vec<u8> {
+        // air spreads down and sideways from the row above the top, like falling rock would
+        let mut air: u8 = 0b111_1111;
+        let mut lowest = self.rows.len();
+        for (i, &row) in self.rows.iter().enumerate().rev().take(MAX_SURFACE) {
+            air &= !row;
+            loop {
+                let spread = air | (air << 1 | air >> 1) & !row & 0b111_1111;
+                if spread == air {
+                    break;
+                }
+                air = spread;
+            }
+            if air == 0 {
+                break;
+            }
+            lowest = i;
+        }
+        self.rows[lowest..].to_vec()
+    }
+
+    /// In `u64`, since a trillion rocks' worth of tower is too tall for a 32-bit `usize`.
+    pub fn do_moves_but_smarter(&mut self, moves: &[Move], n_rocks: u64) -> u64 {
+        trace_span!("do_moves_but_smarter", n_rocks);
         let mut n = 0;
         let mut i_piece = 0;
         let mut i_move = 0;
@@ -180,56 +244,122 @@ impl Tetris {
             i_piece %= PIECES.len();
             i_move %= moves.len();
             n += 1;
-            let latest = self
-                .rows
-                .iter()
-                .rev()
-                .take(16)
-                .fold(0u128, |acc, r| (acc << 8) + *r as u128);
+            let latest = self.surface();
+            stat!("day_17.surface", latest.len());
             // this input sucks
-            if let Some((m, h)) = seen.get(&(i_piece, i_move, latest)) {
+            if let Some((m, h)) = seen.get(&(i_piece, i_move, latest.clone())) {
+                stat!("day_17.cycle_hits", 1);
                 let n_cycles_to_skip = (n_rocks - n) / (n - m);
-                skipped += (self.rows.len() - h) * n_cycles_to_skip;
+                skipped += (self.rows.len() - h) as u64 * n_cycles_to_skip;
                 n += (n - m) * n_cycles_to_skip;
             } else {
                 seen.insert((i_piece, i_move, latest), (n, self.rows.len()));
             }
         }
-        self.rows.len() + skipped
+        self.rows.len() as u64 + skipped
     }
 }
 
 #[aoc_generator(day17)]
-pub fn get_input(input: &str) -> Vec<Move> {
+pub fn get_input(input: &str) -> Result<Vec<Move>, ParseError> {
     input
-        .bytes()
-        .map(|b| if b == b'>' { Move::R } else { Move::L })
+        .char_indices()
+        .map(|(i, c)| match c {
+            '<' => Ok(Move::L),
+            '>' => Ok(Move::R),
+            _ => Err(ParseError::at(input, &input[i..], "`<` or `>`").day(17)),
+        })
         .collect()
 }
 
 #[aoc(day17, part1)]
 pub fn part_1(moves: &[Move]) -> usize {
-    Tetris::new().do_moves(moves, 2022)
+    Tetris::new().do_moves(moves, 2022, &mut Discard)
 }
 
 #[aoc(day17, part2)]
-pub fn part_2(moves: &[Move]) -> usize {
+pub fn part_2(moves: &[Move]) -> u64 {
     Tetris::new().do_moves_but_smarter(moves, 1000000000000)
 }
 
+/// Part 1, one frame per rock coming to rest.
+pub fn animate(moves: &[Move], recorder: &mut impl Recorder) {
+    Tetris::new().do_moves(moves, 2022, recorder);
+}
+
+/// The jet pattern.
+pub struct Jets(pub Vec<Move>);
+
+day!(Jets.0, 17, usize, u64);
+
+/// Both parts by cycle detection, rather than simulating all 2022 rocks for part 1.
+pub struct Cycles;
+
+impl Solution for Cycles {
+    const DAY: u8 = 17;
+    const NAME: &'static str = "cycles";
+
+    type Input = Jets;
+
+    fn parse(input: &str) -> Result<Jets, ParseError> {
+        input.parse()
+    }
+
+    /// As a `usize`, like [`part_1`]'s, so the two answers compare equal.
+    fn part1(jets: &Jets) -> Answer {
+        (Tetris::new().do_moves_but_smarter(&jets.0, 2022) as usize).into()
+    }
+
+    fn part2(jets: &Jets) -> Answer {
+        part_2(&jets.0).into()
+    }
+}
+
+pub static SOLUTIONS: [runner::Day; 2] = [runner::Day::of::<Jets>(), runner::Day::of::<Cycles>()];
+
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     const INPUT: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(&get_input(INPUT)), 3068);
+        assert_eq!(part_1(&get_input(INPUT).unwrap()), 3068);
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(&get_input(INPUT)), 1514285714288);
+        assert_eq!(part_2(&get_input(INPUT).unwrap()), 1514285714288);
+    }
+
+    #[test]
+    fn test_cycles() {
+        let jets = Cycles::parse(INPUT).unwrap();
+        assert_eq!(Cycles::part1(&jets), Answer::Usize(3068));
+        assert_eq!(Cycles::part2(&jets), Answer::U64(1514285714288));
+    }
+
+    #[test]
+    fn test_open_column() {
+        // never fills one of the columns, leaving a shaft down to the floor
+        let moves = get_input("><><>>>>><").unwrap();
+        assert_eq!(part_2(&moves), 1999999999999);
+        assert_eq!(
+            Tetris::new().do_moves_but_smarter(&moves, 10000),
+            Tetris::new().do_moves(&moves, 10000, &mut Discard) as u64
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_smarter_agrees(moves in prop::collection::vec(prop_oneof![Just(Move::L), Just(Move::R)], 1..60), n in 0..400usize) {
+            prop_assert_eq!(
+                Tetris::new().do_moves_but_smarter(&moves, n as u64),
+                Tetris::new().do_moves(&moves, n, &mut Discard) as u64
+            );
+        }
     }
 }
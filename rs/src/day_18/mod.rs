@@ -2,11 +2,16 @@ use std::collections::HashSet;
 
 use sscanf::scanf;
 
+use crate::{error::ParseError, runner};
+
 #[aoc_generator(day18)]
-pub fn get_input(input: &str) -> HashSet<(isize, isize, isize)> {
+pub fn get_input(input: &str) -> Result<HashSet<(isize, isize, isize)>, ParseError> {
     input
         .lines()
-        .map(|l| scanf!(l, "{isize},{isize},{isize}").unwrap())
+        .map(|l| {
+            scanf!(l, "{isize},{isize},{isize}")
+                .map_err(|_| ParseError::at(input, l, "`x,y,z` coordinates").day(18))
+        })
         .collect()
 }
 
@@ -75,19 +80,38 @@ pub fn part_2(cubes: &HashSet<(isize, isize, isize)>) -> usize {
         .count()
 }
 
+/// The cubes making up the droplet.
+pub struct Droplet(pub HashSet<(isize, isize, isize)>);
+
+day!(Droplet.0, 18, usize, usize);
+
+pub static SOLUTIONS: [runner::Day; 1] = [runner::Day::of::<Droplet>()];
+
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
+    use crate::inputgen;
 
     const INPUT: &str = include_str!("../../test_data/day_18.txt");
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(&get_input(INPUT)), 64);
+        assert_eq!(part_1(&get_input(INPUT).unwrap()), 64);
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(&get_input(INPUT)), 58);
+        assert_eq!(part_2(&get_input(INPUT).unwrap()), 58);
+    }
+
+    proptest! {
+        #[test]
+        fn test_exterior_within_surface(input in inputgen::arbitrary(18, 1..=40)) {
+            let cubes = get_input(&input).unwrap();
+            prop_assert!(part_2(&cubes) <= part_1(&cubes));
+            prop_assert!(part_1(&cubes) <= 6 * cubes.len());
+        }
     }
 }
@@ -13,6 +13,11 @@ use nom::{
 };
 use num::{CheckedSub, Zero};
 
+use crate::{
+    error::{finish, ParseError},
+    runner,
+};
+
 #[derive(Debug)]
 pub struct Blueprint<T> {
     n: usize,
@@ -120,7 +125,13 @@ impl<T> IndexMut<usize> for R4<T> {
 }
 
 impl Blueprint<usize> {
-    fn max_geodes(&self, t_0: usize) -> usize {
+    pub fn id(&self) -> usize {
+        self.n
+    }
+
+    /// The most geodes that can be cracked in `t_0` minutes, starting with one ore robot.
+    pub fn max_geodes(&self, t_0: usize) -> usize {
+        trace_span!("max_geodes", blueprint = self.n, minutes = t_0);
         let mut q = vec![(t_0, R4(1, 0, 0, 0), R4::zero())];
         let mut result = 0;
         let max_costs = self
@@ -128,19 +139,18 @@ impl Blueprint<usize> {
             .iter()
             .fold(R4::zero(), |acc, cost| acc.element_max(cost));
         while let Some((t, r, n)) = q.pop() {
+            stat!("day_19.queue", q.len());
             for i in 0..4 {
                 if max_costs[i] != 0 && r[i] >= max_costs[i] {
                     continue;
                 }
-                let t_cost_opt = (0..4).fold(Some(0), |acc, j| {
+                let t_cost_opt = (0..4).try_fold(0, |acc, j| {
                     if self.costs[i][j] == 0 {
-                        acc
-                    } else if r[j] == 0 {
-                        None
+                        Some(acc)
                     } else {
-                        acc.map(|a| {
-                            a.max((self.costs[i][j] + r[j] - 1).saturating_sub(n[j]) / r[j])
-                        })
+                        // no robots making it yet means no amount of waiting will do
+                        let short = (self.costs[i][j] + r[j] - 1).saturating_sub(n[j]);
+                        short.checked_div(r[j]).map(|t| acc.max(t))
                     }
                 });
                 if let Some(t_cost) = t_cost_opt {
@@ -151,8 +161,10 @@ impl Blueprint<usize> {
                         result = result.max(n_new[3]);
                         let t_new = t - t_cost - 1;
                         let could_make = n_new[3] + r_new[3] * t_new + (t_new * t_new + t_new) / 2;
+                        stat!("day_19.candidates", 1);
                         // TODO: add more pruning
                         if t_new > 0 && could_make > result + 1 {
+                            stat!("day_19.pushed", 1);
                             q.push((t_new, r_new, n_new - self.costs[i]));
                         }
                     }
@@ -162,7 +174,7 @@ impl Blueprint<usize> {
         result
     }
 
-    fn quality(&self, t_0: usize) -> usize {
+    pub fn quality(&self, t_0: usize) -> usize {
         self.n * self.max_geodes(t_0)
     }
 }
@@ -198,8 +210,8 @@ fn parse_blueprints(s: &str) -> IResult<&str, Vec<Blueprint<usize>>> {
 }
 
 #[aoc_generator(day19)]
-pub fn get_input(input: &str) -> Vec<Blueprint<usize>> {
-    parse_blueprints(input).unwrap().1
+pub fn get_input(input: &str) -> Result<Vec<Blueprint<usize>>, ParseError> {
+    finish(input, parse_blueprints(input)).map_err(|e| e.day(19))
 }
 
 #[aoc(day19, part1)]
@@ -216,6 +228,13 @@ pub fn part_2(blueprints: &[Blueprint<usize>]) -> usize {
         .product()
 }
 
+/// The blueprints, in order, since part 2 only uses the first three.
+pub struct Blueprints(pub Vec<Blueprint<usize>>);
+
+day!(Blueprints.0, 19, usize, usize);
+
+pub static SOLUTIONS: [runner::Day; 1] = [runner::Day::of::<Blueprints>()];
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -224,12 +243,12 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(&get_input(INPUT)), 33);
+        assert_eq!(part_1(&get_input(INPUT).unwrap()), 33);
     }
 
     #[test]
     #[ignore] // slow
     fn test_part_2() {
-        assert_eq!(part_2(&get_input(INPUT)), 3472);
+        assert_eq!(part_2(&get_input(INPUT).unwrap()), 3472);
     }
 }
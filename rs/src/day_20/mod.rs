@@ -1,15 +1,19 @@
-fn mix(ns: &[isize], indices: &mut Vec<usize>) {
+use crate::{error::ParseError, runner};
+
+/// `i64` rather than `isize`, since part 2's numbers are too big for 32 bits.
+fn mix(ns: &[i64], indices: &mut Vec<usize>) {
+    // with one number there's nowhere to move it, and nothing to take the remainder by
+    if ns.len() < 2 {
+        return;
+    }
     for (i, &n) in ns.iter().enumerate() {
         let j = indices.iter().position(|&n| n == i).unwrap();
         indices.remove(j);
-        indices.insert(
-            (j as isize + n).rem_euclid(ns.len() as isize - 1) as usize,
-            i,
-        );
+        indices.insert((j as i64 + n).rem_euclid(ns.len() as i64 - 1) as usize, i);
     }
 }
 
-fn answer_from_decrypted(ns: &[isize], indices: &[usize]) -> isize {
+fn answer_from_decrypted(ns: &[i64], indices: &[usize]) -> i64 {
     let zero = indices
         .iter()
         .position(|&i| i == ns.iter().position(|&n| n == 0).unwrap())
@@ -20,38 +24,91 @@ fn answer_from_decrypted(ns: &[isize], indices: &[usize]) -> isize {
 }
 
 #[aoc_generator(day20)]
-pub fn get_input(input: &str) -> Vec<isize> {
-    input.lines().map(|l| l.parse().unwrap()).collect()
+pub fn get_input(input: &str) -> Result<Vec<i64>, ParseError> {
+    let ns = input
+        .lines()
+        .map(|l| {
+            l.parse()
+                .map_err(|_| ParseError::at(input, l, "integer").day(20))
+        })
+        .collect::<Result<Vec<i64>, _>>()?;
+    if !ns.contains(&0) {
+        return Err(ParseError::at_end(input, "a 0 in the file").day(20));
+    }
+    Ok(ns)
 }
 
 #[aoc(day20, part1)]
-pub fn part_1(ns: &Vec<isize>) -> isize {
+pub fn part_1(ns: &[i64]) -> i64 {
     let mut indices: Vec<usize> = (0..ns.len()).collect();
-    mix(&ns, &mut indices);
-    answer_from_decrypted(&ns, &indices)
+    mix(ns, &mut indices);
+    answer_from_decrypted(ns, &indices)
 }
 
 #[aoc(day20, part2)]
-pub fn part_2(ns: &[isize]) -> isize {
+pub fn part_2(ns: &[i64]) -> i64 {
     let ns = ns.iter().map(|&n| n * 811589153).collect::<Vec<_>>();
     let mut indices: Vec<usize> = (0..ns.len()).collect();
     (0..10).for_each(|_| mix(&ns, &mut indices));
     answer_from_decrypted(&ns, &indices)
 }
 
+/// The file's numbers, in their original order.
+pub struct EncryptedFile(pub Vec<i64>);
+
+day!(EncryptedFile.0, 20, i64, i64);
+
+pub static SOLUTIONS: [runner::Day; 1] = [runner::Day::of::<EncryptedFile>()];
+
 #[cfg(test)]
 mod tests {
+    use std::collections::VecDeque;
+
+    use proptest::prelude::*;
+
     use super::*;
 
     const INPUT: &str = "1\n2\n-3\n3\n-2\n0\n4";
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(&get_input(INPUT)), 3);
+        assert_eq!(part_1(&get_input(INPUT).unwrap()), 3);
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(&get_input(INPUT)), 1623178306);
+        assert_eq!(part_2(&get_input(INPUT).unwrap()), 1623178306);
+    }
+
+    /// Mixing by turning a ring of the original indices, for `mix` to be checked against. Gives
+    /// the order starting from the first number, since a ring has no start of its own.
+    fn mix_by_rotating(ns: &[i64]) -> Vec<usize> {
+        let mut ring: VecDeque<usize> = (0..ns.len()).collect();
+        for (i, &n) in ns.iter().enumerate() {
+            let j = ring.iter().position(|&k| k == i).unwrap();
+            ring.rotate_left(j);
+            ring.pop_front();
+            if !ring.is_empty() {
+                ring.rotate_left(n.rem_euclid(ring.len() as i64) as usize);
+            }
+            ring.push_front(i);
+        }
+        let first = ring.iter().position(|&k| k == 0).unwrap();
+        ring.rotate_left(first);
+        ring.into()
+    }
+
+    proptest! {
+        #[test]
+        fn test_mix(ns in prop::collection::vec(-50i64..50, 1..40)) {
+            let mut indices: Vec<usize> = (0..ns.len()).collect();
+            mix(&ns, &mut indices);
+            let mut sorted = indices.clone();
+            sorted.sort();
+            prop_assert_eq!(sorted, (0..ns.len()).collect::<Vec<_>>());
+            let first = indices.iter().position(|&k| k == 0).unwrap();
+            indices.rotate_left(first);
+            prop_assert_eq!(indices, mix_by_rotating(&ns));
+        }
     }
 }
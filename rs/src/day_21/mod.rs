@@ -16,6 +16,11 @@ use nom::{
     IResult,
 };
 
+use crate::{
+    error::{finish, ParseError},
+    runner,
+};
+
 #[derive(Clone, Copy, Debug)]
 pub enum Op {
     Add,
@@ -152,6 +157,8 @@ impl<
     }
 }
 
+type MonkeyTable = HashMap<Vec<u8>, Job<i64, Vec<u8>>>;
+
 struct Monkey<T, I> {
     id: Vec<u8>,
     job: Job<T, I>,
@@ -189,23 +196,35 @@ fn parse_job<T: FromStr>(s: &str) -> IResult<&str, Job<T, Vec<u8>>> {
 }
 
 #[aoc_generator(day21)]
-pub fn get_input(input: &str) -> HashMap<Vec<u8>, Job<isize, Vec<u8>>> {
-    parse_monkeys(input)
-        .unwrap()
-        .1
+pub fn get_input(input: &str) -> Result<MonkeyTable, ParseError> {
+    let monkeys = finish(input, parse_monkeys(input)).map_err(|e| e.day(21))?;
+    let table: MonkeyTable = monkeys
         .iter()
         .map(|monkey| (monkey.id.clone(), monkey.job.clone()))
-        .collect()
+        .collect();
+    for name in table.values().flat_map(|job| match job {
+        Job::Const(_) => vec![],
+        Job::Expr(_, a, b) => vec![a, b],
+    }) {
+        if !table.contains_key(name) {
+            let expected = format!("a monkey named `{}`", String::from_utf8_lossy(name));
+            return Err(ParseError::at_end(input, expected).day(21));
+        }
+    }
+    if !table.contains_key(b"root".as_slice()) {
+        return Err(ParseError::at_end(input, "a monkey named `root`").day(21));
+    }
+    Ok(table)
 }
 
 #[aoc(day21, part1)]
-pub fn part_1(monkey_table: &HashMap<Vec<u8>, Job<isize, Vec<u8>>>) -> isize {
+pub fn part_1(monkey_table: &HashMap<Vec<u8>, Job<i64, Vec<u8>>>) -> i64 {
     monkey_table["root".as_bytes()].eval(monkey_table)
 }
 
 #[aoc(day21, part2)]
-pub fn part_2(monkey_table: &HashMap<Vec<u8>, Job<isize, Vec<u8>>>) -> isize {
-    let mut monkey_table: HashMap<&[u8], Job<Option<isize>, &[u8]>> =
+pub fn part_2(monkey_table: &HashMap<Vec<u8>, Job<i64, Vec<u8>>>) -> i64 {
+    let mut monkey_table: HashMap<&[u8], Job<Option<i64>, &[u8]>> =
         HashMap::from_iter(monkey_table.iter().map(|(k, v)| {
             (
                 k.as_slice(),
@@ -231,6 +250,13 @@ pub fn part_2(monkey_table: &HashMap<Vec<u8>, Job<isize, Vec<u8>>>) -> isize {
     }
 }
 
+/// Each monkey's job, by name.
+pub struct Monkeys(pub HashMap<Vec<u8>, Job<i64, Vec<u8>>>);
+
+day!(Monkeys.0, 21, i64, i64);
+
+pub static SOLUTIONS: [runner::Day; 1] = [runner::Day::of::<Monkeys>()];
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -239,13 +265,13 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(&get_input(INPUT)), 152);
+        assert_eq!(part_1(&get_input(INPUT).unwrap()), 152);
     }
 
     #[test]
     fn test_job_solve_for_none() {
         // set up simple expression tree corresponding to (1 + (2 * x)) == 7
-        let mut id_table: HashMap<&[u8], Job<Option<isize>, &[u8]>> = HashMap::new();
+        let mut id_table: HashMap<&[u8], Job<Option<i64>, &[u8]>> = HashMap::new();
         let (a, b, c, d, x) = (b"a", b"b", b"c", b"d", b"x");
         id_table.insert(x, Job::Const(None));
         id_table.insert(a, Job::Const(Some(1)));
@@ -257,6 +283,6 @@ mod tests {
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(&get_input(INPUT)), 301);
+        assert_eq!(part_2(&get_input(INPUT).unwrap()), 301);
     }
 }
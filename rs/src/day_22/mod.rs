@@ -1,8 +1,7 @@
 // this is so ugly
 
-use std::{collections::HashMap, num::ParseIntError};
+use std::{collections::HashMap, fmt, num::ParseIntError};
 
-use itertools::Itertools;
 use nom::{
     self,
     branch::alt,
@@ -12,9 +11,14 @@ use nom::{
     multi::{many1, separated_list1},
     IResult,
 };
-use num::{Complex, Integer};
+use num::Complex;
 
-use crate::grid::Grid;
+use crate::{
+    error::{finish, ParseError},
+    grid::Grid,
+    render::{Canvas, Glyph, RED},
+    runner,
+};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum Tile {
@@ -23,6 +27,16 @@ enum Tile {
     Wall,
 }
 
+impl Glyph for Tile {
+    fn glyph(&self) -> char {
+        match self {
+            Tile::Void => ' ',
+            Tile::Open => '.',
+            Tile::Wall => '#',
+        }
+    }
+}
+
 fn parse_tile(s: &str) -> IResult<&str, Tile> {
     map_res(one_of(" .#"), |c: char| {
         Ok::<_, nom::Err<(&str, nom::error::ErrorKind)>>(match c {
@@ -35,13 +49,8 @@ fn parse_tile(s: &str) -> IResult<&str, Tile> {
 }
 
 fn parse_tile_grid(s: &str) -> IResult<&str, Grid<Tile>> {
-    map(separated_list1(line_ending, many1(parse_tile)), |v| {
-        let w = v.iter().map(|l| l.len()).max().unwrap().max(v.len());
-        let items = (0..w)
-            .cartesian_product(0..w)
-            .map(|(i, j)| *v.get(i).and_then(|r| r.get(j)).unwrap_or(&Tile::Void))
-            .collect();
-        Grid { items, w }
+    map(separated_list1(line_ending, many1(parse_tile)), |rows| {
+        Grid::from_rows(rows, Tile::Void)
     })(s)
 }
 
@@ -102,41 +111,14 @@ pub struct State {
 }
 
 impl State {
-    fn c_concave_corner(&self, c: C) -> Option<C> {
-        if let Some(Tile::Void) = self.get(c) {
-            return None;
-        }
-        let p = self
-            .map
-            .neighbors(self.map.xy_to_i((c.re as usize, c.im as usize)))
-            .iter()
-            .filter(|(_, tile)| *tile != Tile::Void)
-            .count()
-            == 4;
-        let diagonals = self
-            .map
-            .neighbors_diagonal(self.map.xy_to_i((c.re as usize, c.im as usize)));
-        let voids: Vec<&(usize, Tile)> = diagonals
-            .iter()
-            .filter(|(_, tile)| *tile == Tile::Void)
-            .collect();
-        if p && voids.len() == 1 {
-            let (y, x) = voids[0].0.div_rem(&self.map.w);
-            Some(complex!(x as isize, y as isize))
-        } else {
-            None
-        }
-    }
-
     // TODO: both follow_directions_X functions should accept a wrap map
     fn follow_directions_flat(&mut self) -> &mut Self {
         // {position => (direction => new position)}
         let mut wrap_map: FlatWrapMap = HashMap::new();
-        for i_row in (0..self.map.items.len()).step_by(self.map.w) {
-            let row = &self.map.items[i_row..(i_row + self.map.w)];
+        for (y, row) in self.map.rows().enumerate() {
             if let Some(j_l) = row.iter().position(|&tile| tile != Tile::Void) {
-                let y = (i_row / self.map.w) as isize;
-                let j_r = self.map.w
+                let y = y as isize;
+                let j_r = row.len()
                     - row
                         .iter()
                         .rev()
@@ -153,10 +135,9 @@ impl State {
                     .insert(complex!(1, 0), complex!(j_l as isize, y));
             }
         }
-        for i_col in 0..self.map.w {
-            let col_iter = self.map.items.iter().skip(i_col).step_by(self.map.w);
+        for (i_col, col_iter) in self.map.columns().enumerate() {
             if let Some(j_u) = col_iter.clone().position(|&tile| tile != Tile::Void) {
-                let j_d = self.map.w
+                let j_d = self.map.h()
                     - col_iter
                         .clone()
                         .rev()
@@ -180,11 +161,7 @@ impl State {
                 Direction::Go(n) => {
                     'go: for _ in 0..n {
                         let dst = self.sprite.0 + self.sprite.1;
-                        if dst.re < 0
-                            || dst.im < 0
-                            || self.map.xy_to_i((dst.re as usize, dst.im as usize))
-                                >= self.map.items.len()
-                        {
+                        if self.get(dst).is_none() {
                             let dst_wrap = wrap_map[&self.sprite.0][&self.sprite.1];
                             self.sprite.0 = match self.get(dst_wrap).unwrap() {
                                 Tile::Open => dst_wrap,
@@ -199,10 +176,7 @@ impl State {
                             self.sprite.0 = match self.get(dst).unwrap() {
                                 Tile::Void => {
                                     let dst_wrap = wrap_map[&self.sprite.0][&self.sprite.1];
-                                    let i_wrap = self
-                                        .map
-                                        .xy_to_i((dst_wrap.re as usize, dst_wrap.im as usize));
-                                    match self.map.items[i_wrap] {
+                                    match self.get(dst_wrap).unwrap() {
                                         Tile::Open => dst_wrap,
                                         Tile::Wall => {
                                             // can stop this Go early
@@ -227,99 +201,82 @@ impl State {
     }
 
     fn get(&self, c: C) -> Option<Tile> {
-        if c.re < 0
-            || c.im < 0
-            || self.map.xy_to_i((c.re as usize, c.im as usize)) >= self.map.items.len()
-        {
-            None
-        } else {
-            Some(self.map.items[self.map.xy_to_i((c.re as usize, c.im as usize))])
-        }
+        let xy = (usize::try_from(c.re).ok()?, usize::try_from(c.im).ok()?);
+        self.map.get_xy(xy).copied()
     }
 
-    // CAN'T BELIEVE THIS WORKED ON THE FIRST TRY
-    fn follow_directions_cube(&mut self) -> &mut Self {
-        // {position => (direction => (new position, new direction))}
-        let mut wrap_map: CubeWrapMap = HashMap::new();
-        for (i, j) in (0..self.map.w).cartesian_product(0..self.map.w) {
-            let c = complex!(i as isize, j as isize);
-            if let Some(void) = self.c_concave_corner(c) {
-                let diff = void - c;
-                let mut direction_a = complex!(diff.re, 0);
-                let mut direction_b = complex!(0, diff.im);
-                let mut direction_a_norm = direction_b;
-                let mut direction_b_norm = direction_a;
-                let mut a = c + direction_a;
-                let mut b = c + direction_b;
-                loop {
-                    wrap_map
-                        .entry(a)
-                        .or_default()
-                        .insert(direction_a_norm, (b, -direction_b_norm));
-                    wrap_map
-                        .entry(b)
-                        .or_default()
-                        .insert(direction_b_norm, (a, -direction_a_norm));
-                    let mut a_turned = false;
-                    let mut b_turned = false;
-                    if self
-                        .get(a + direction_a)
-                        .map(|a| a == Tile::Void)
-                        .unwrap_or(true)
-                    {
-                        a_turned = true;
-                        if self
-                            .get(a + direction_a * complex!(0, 1))
-                            .map(|a| a != Tile::Void)
-                            .unwrap_or(false)
-                        {
-                            direction_a *= complex!(0, 1);
-                            direction_a_norm *= complex!(0, 1);
-                        } else {
-                            direction_a *= complex!(0, -1);
-                            direction_a_norm *= complex!(0, -1);
-                        }
-                    }
-                    if self
-                        .get(b + direction_b)
-                        .map(|b| b == Tile::Void)
-                        .unwrap_or(true)
-                    {
-                        b_turned = true;
-                        if self
-                            .get(b + direction_b * complex!(0, 1))
-                            .map(|b| b != Tile::Void)
-                            .unwrap_or(false)
-                        {
-                            direction_b *= complex!(0, 1);
-                            direction_b_norm *= complex!(0, 1);
-                        } else {
-                            direction_b *= complex!(0, -1);
-                            direction_b_norm *= complex!(0, -1);
-                        }
-                    }
-                    match (a_turned, b_turned) {
-                        (true, true) => break,
-                        (true, false) => b += direction_b,
-                        (false, true) => a += direction_a,
-                        (false, false) => {
-                            a += direction_a;
-                            b += direction_b;
-                        }
-                    }
-                }
+    /// Where stepping off each edge of the net lands on the cube, and facing which way. The net's
+    /// outline is walked one face's edge at a time, noting the angle inside it at each corner in
+    /// quarter turns. The two edges at a corner of three quarters have all three of a cube
+    /// vertex's faces around it, so they're one edge of the cube: they get zipped together and
+    /// dropped, leaving the corner between their neighbors with both of those angles. A cube net
+    /// always has another such corner until every edge is zipped.
+    fn cube_wrap_map(&self) -> CubeWrapMap {
+        let tiles = self.map.items.iter().filter(|&&t| t != Tile::Void).count();
+        let n = (1..).find(|n| 6 * n * n >= tiles).unwrap() as isize;
+        let is_face = |f: C| self.get(f * n).is_some_and(|t| t != Tile::Void);
+        // (face, direction along the edge, outward normal), clockwise from the first face's top
+        let first = (
+            complex!((0..).find(|&x| is_face(complex!(x, 0))).unwrap(), 0),
+            complex!(1, 0),
+            complex!(0, -1),
+        );
+        let mut edges = vec![];
+        let (mut f, mut d, mut m) = first;
+        loop {
+            let edge = (f, d, m);
+            let angle = if is_face(f + d + m) {
+                f += d + m;
+                (d, m) = (m, -d);
+                3
+            } else if is_face(f + d) {
+                f += d;
+                2
+            } else {
+                (d, m) = (-m, d);
+                1
             };
+            edges.push((edge, angle));
+            if (f, d, m) == first {
+                break;
+            }
+        }
+        // the `k`th tile along an edge, starting from the corner on the outward side
+        let tile = |(f, d, m): (C, C, C), k: isize| {
+            let corner = |v: isize| if v > 0 { n - 1 } else { 0 };
+            let v = m - d;
+            f * n + complex!(corner(v.re), corner(v.im)) + d * k
+        };
+        let mut wrap_map: CubeWrapMap = HashMap::new();
+        while !edges.is_empty() {
+            let i = edges
+                .iter()
+                .position(|&(_, angle)| angle == 3)
+                .expect("a cube net");
+            let j = (i + 1) % edges.len();
+            let ((a, _), (b, b_angle)) = (edges[i], edges[j]);
+            // `a` ends at the corner and `b` starts there
+            for k in 0..n {
+                let (from, to) = (tile(a, n - 1 - k), tile(b, k));
+                wrap_map.entry(from).or_default().insert(a.2, (to, -b.2));
+                wrap_map.entry(to).or_default().insert(b.2, (from, -a.2));
+            }
+            let before = (i + edges.len() - 1) % edges.len();
+            edges[before].1 += b_angle;
+            edges.remove(i.max(j));
+            edges.remove(i.min(j));
         }
+        wrap_map
+    }
+
+    fn follow_directions_cube(&mut self) -> &mut Self {
+        let wrap_map = self.cube_wrap_map();
         for d in self.directions.iter().copied() {
             match d {
                 Direction::Go(n) => {
                     'go: for _ in 0..n {
                         let dst = self.sprite.0 + self.sprite.1;
-                        if dst.re < 0
-                            || dst.im < 0
-                            || self.map.xy_to_i((dst.re as usize, dst.im as usize))
-                                >= self.map.items.len()
-                        {
+                        if self.get(dst).is_none() {
                             let dst = wrap_map[&self.sprite.0][&self.sprite.1];
                             match self.get(dst.0).unwrap() {
                                 Tile::Open => {
@@ -362,6 +319,19 @@ impl State {
         self
     }
 
+    /// The board, with where the path has got to as an arrow the way it's facing.
+    pub fn draw(&self) -> Canvas {
+        let facing = match (self.sprite.1.re, self.sprite.1.im) {
+            (1, 0) => '>',
+            (0, 1) => 'v',
+            (-1, 0) => '<',
+            _ => '^',
+        };
+        let mut canvas = Canvas::from_grid(&self.map);
+        canvas.highlight([(self.sprite.0.re, self.sprite.0.im)], (facing, RED));
+        canvas
+    }
+
     fn password(&self) -> isize {
         1000 * (self.sprite.0.im + 1)
             + 4 * (self.sprite.0.re + 1)
@@ -376,18 +346,20 @@ impl State {
 }
 
 impl TryFrom<&str> for State {
-    type Error = String;
+    type Error = ParseError;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
-        let (s_map, s_directions) = s.split_once("\n\n").ok_or("")?;
-        let (_, map) = parse_tile_grid(s_map).map_err(|e| format!("error parsing map: {:?}", e))?;
-        let (_, directions) = parse_directions(s_directions)
-            .map_err(|e| format!("error parsing directions: {:?}", e))?;
+        let (s_map, s_directions) = s
+            .split_once("\n\n")
+            .ok_or_else(|| ParseError::at_end(s, "blank line after the map").day(22))?;
+        let map = finish(s, parse_tile_grid(s_map)).map_err(|e| e.day(22))?;
+        let directions = finish(s, parse_directions(s_directions)).map_err(|e| e.day(22))?;
         let start_c = complex!(
-            map.items
+            map.items[..map.w]
                 .iter()
                 .position(|&t| t == Tile::Open)
-                .ok_or("no open tile on first line")? as isize,
+                .ok_or_else(|| ParseError::at(s, s, "open tile on the first line").day(22))?
+                as isize,
             0
         );
         let sprite = (start_c, complex!(1, 0));
@@ -399,9 +371,15 @@ impl TryFrom<&str> for State {
     }
 }
 
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.draw().fmt(f)
+    }
+}
+
 #[aoc_generator(day22)]
-pub fn get_input(input: &str) -> State {
-    input.try_into().unwrap()
+pub fn get_input(input: &str) -> Result<State, ParseError> {
+    input.try_into()
 }
 
 #[aoc(day22, part1)]
@@ -414,6 +392,10 @@ pub fn part_2(state: &State) -> isize {
     state.clone().follow_directions_cube().password()
 }
 
+day!(State, 22, isize, isize);
+
+pub static SOLUTIONS: [runner::Day; 1] = [runner::Day::of::<State>()];
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -421,20 +403,43 @@ mod tests {
     const INPUT: &str = include_str!("../../test_data/day_22.txt");
 
     #[test]
-    fn test_state_impl() {
-        let state = get_input(INPUT);
-        assert!(state.c_concave_corner(complex!(8, 0)).is_none());
-        assert!(state.c_concave_corner(complex!(8, 4)).is_some());
-        assert!(state.c_concave_corner(complex!(9, 4)).is_none());
+    fn test_cube_wrap_map() {
+        let wrap_map = get_input(INPUT).unwrap().cube_wrap_map();
+        // the puzzle's A and C
+        assert_eq!(
+            wrap_map[&complex!(11, 5)][&complex!(1, 0)],
+            (complex!(14, 8), complex!(0, 1))
+        );
+        assert_eq!(
+            wrap_map[&complex!(10, 11)][&complex!(0, 1)],
+            (complex!(1, 7), complex!(0, -1))
+        );
+        // 14 face edges' worth of tiles, each stepped off one way and back the other
+        assert_eq!(wrap_map.values().map(HashMap::len).sum::<usize>(), 14 * 4);
+        for (&c, wraps) in wrap_map.iter() {
+            for (&d, &(c_wrap, d_wrap)) in wraps.iter() {
+                assert_eq!(wrap_map[&c_wrap][&-d_wrap], (c, -d));
+            }
+        }
     }
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(&get_input(INPUT)), 6032);
+        assert_eq!(part_1(&get_input(INPUT).unwrap()), 6032);
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(&get_input(INPUT)), 5031);
+        assert_eq!(part_2(&get_input(INPUT).unwrap()), 5031);
+    }
+
+    #[test]
+    fn test_draw() {
+        let mut state = get_input(INPUT).unwrap();
+        let text = state.to_string();
+        assert_eq!(text.lines().next().unwrap().trim_end(), "        >..#");
+        // the puzzle's final position on the cube, row 5 and column 7 facing up
+        let text = state.follow_directions_cube().to_string();
+        assert_eq!(text.lines().nth(4).unwrap().trim_end(), "...#..^....#");
     }
 }
@@ -1,7 +1,14 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, fmt};
 
 use num::Integer;
 
+use crate::{
+    error::ParseError,
+    record::{Discard, Recorder},
+    render::Canvas,
+    runner,
+};
+
 type Point = (isize, isize);
 const CHECKS: [([Point; 3], Point); 4] = [
     // first three tuples are where to check in the grid, last is where to move to
@@ -28,8 +35,19 @@ impl ElfMap {
         self
     }
 
-    fn first_no_move_round(&mut self) -> usize {
-        1 + (0..).map(|i| self.step(i)).take_while(|b| *b).count()
+    fn first_no_move_round(&mut self, recorder: &mut impl Recorder) -> usize {
+        1 + (0..)
+            .map(|i| {
+                let moved = self.step(i);
+                recorder.record(|| self.draw());
+                moved
+            })
+            .take_while(|b| *b)
+            .count()
+    }
+
+    fn draw(&self) -> Canvas {
+        Canvas::from_points(self.es.iter().copied(), '#', '.')
     }
 
     fn step(&mut self, n: usize) -> bool {
@@ -70,10 +88,10 @@ impl ElfMap {
             (isize::MAX, isize::MAX, isize::MIN, isize::MIN, 0),
             |acc, (x, y)| {
                 (
-                    acc.0.min(*x as isize),
-                    acc.1.min(*y as isize),
-                    acc.2.max(*x as isize),
-                    acc.3.max(*y as isize),
+                    acc.0.min(*x),
+                    acc.1.min(*y),
+                    acc.2.max(*x),
+                    acc.3.max(*y),
                     acc.4 + 1,
                 )
             },
@@ -87,43 +105,43 @@ impl ElfMap {
 }
 
 impl TryFrom<&str> for ElfMap {
-    type Error = String;
+    type Error = ParseError;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
-        let n = s.split_once('\n').ok_or("")?.0.len();
-        let es = HashSet::from_iter(s.bytes().filter(|c| *c != b'\n').enumerate().filter_map(
-            |(i, c)| {
-                (c == b'#').then(|| {
+        let n = s
+            .lines()
+            .next()
+            .ok_or_else(|| ParseError::at_end(s, "row of the map").day(23))?
+            .len();
+        if let Some(i) = s.find(|c| !"#.\n".contains(c)) {
+            return Err(ParseError::at(s, &s[i..], "`#` or `.`").day(23));
+        }
+        if let Some(l) = s.lines().find(|l| l.len() != n) {
+            return Err(ParseError::at(s, l, format!("row of width {}", n)).day(23));
+        }
+        let es = HashSet::from_iter(
+            s.bytes()
+                .filter(|c| *c != b'\n')
+                .enumerate()
+                .filter(|&(_, c)| c == b'#')
+                .map(|(i, _)| {
                     let (y, x) = i.div_rem(&n);
                     (x as isize, y as isize)
-                })
-            },
-        ));
+                }),
+        );
         Ok(Self { es })
     }
 }
 
-impl From<&ElfMap> for String {
-    fn from(elves: &ElfMap) -> Self {
-        let mut result: Vec<char> = Vec::new();
-        let (min_x, min_y, max_x, max_y, _) = elves.bounds();
-        for x in min_x..max_x {
-            for y in min_y..max_y {
-                if elves.es.contains(&(x, y)) {
-                    result.push('#')
-                } else {
-                    result.push('.')
-                }
-            }
-            result.push('\n')
-        }
-        result.into_iter().collect()
+impl fmt::Display for ElfMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.draw().fmt(f)
     }
 }
 
 #[aoc_generator(day23)]
-pub fn get_input(input: &str) -> ElfMap {
-    input.try_into().unwrap()
+pub fn get_input(input: &str) -> Result<ElfMap, ParseError> {
+    input.try_into()
 }
 
 #[aoc(day23, part1)]
@@ -133,9 +151,18 @@ pub fn part_1(map: &ElfMap) -> usize {
 
 #[aoc(day23, part2)]
 pub fn part_2(map: &ElfMap) -> usize {
-    map.clone().first_no_move_round()
+    map.clone().first_no_move_round(&mut Discard)
 }
 
+/// Part 2, one frame per round.
+pub fn animate(map: &ElfMap, recorder: &mut impl Recorder) {
+    map.clone().first_no_move_round(recorder);
+}
+
+day!(ElfMap, 23, usize, usize);
+
+pub static SOLUTIONS: [runner::Day; 1] = [runner::Day::of::<ElfMap>()];
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -144,11 +171,11 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(&get_input(INPUT)), 110);
+        assert_eq!(part_1(&get_input(INPUT).unwrap()), 110);
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(&get_input(INPUT)), 20);
+        assert_eq!(part_2(&get_input(INPUT).unwrap()), 20);
     }
 }
@@ -2,6 +2,14 @@ use std::collections::HashSet;
 
 use num::Complex;
 
+use crate::{
+    error::ParseError,
+    grid::{Grid, Topology},
+    record::{Discard, Recorder},
+    render::Canvas,
+    runner,
+};
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 enum Dir {
     U,
@@ -26,6 +34,17 @@ impl TryFrom<char> for Dir {
     }
 }
 
+impl Dir {
+    fn offset(self) -> (isize, isize) {
+        match self {
+            Dir::U => (0, -1),
+            Dir::D => (0, 1),
+            Dir::L => (-1, 0),
+            Dir::R => (1, 0),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Map {
     bs: HashSet<(Complex<usize>, Dir)>,
@@ -34,53 +53,32 @@ pub struct Map {
 }
 
 impl Map {
+    fn step(&self, topology: Topology, c: Complex<usize>, d: Dir) -> Option<Complex<usize>> {
+        let (x, y) = topology.step((c.re, c.im), d.offset(), (self.w, self.h))?;
+        Some(complex!(x, y))
+    }
+
+    /// Blizzards wrap around the valley, so it's a torus to them.
     fn move_blizzard(&self, (c, d): (Complex<usize>, Dir)) -> (Complex<usize>, Dir) {
-        match d {
-            Dir::U => {
-                if c.im == 0 {
-                    (complex!(c.re, self.h - 1), d)
-                } else {
-                    (complex!(c.re, c.im - 1), d)
-                }
-            }
-            Dir::D => {
-                if c.im == self.h - 1 {
-                    (complex!(c.re, 0), d)
-                } else {
-                    (complex!(c.re, c.im + 1), d)
-                }
-            }
-            Dir::L => {
-                if c.re == 0 {
-                    (complex!(self.w - 1, c.im), d)
-                } else {
-                    (complex!(c.re - 1, c.im), d)
-                }
-            }
-            Dir::R => {
-                if c.re == self.w - 1 {
-                    (complex!(0, c.im), d)
-                } else {
-                    (complex!(c.re + 1, c.im), d)
-                }
-            }
-        }
+        (self.step(Topology::Torus, c, d).unwrap(), d)
     }
 
     fn move_elf(&self, c: Complex<usize>, d: Dir) -> Option<Complex<usize>> {
-        match d {
-            Dir::U => c.im.checked_sub(1).map(|im| complex!(c.re, im)),
-            Dir::D => (c.im < self.h - 1).then_some(complex!(c.re, c.im + 1)),
-            Dir::L => c.re.checked_sub(1).map(|re| complex!(re, c.im)),
-            Dir::R => (c.re < self.w - 1).then_some(complex!(c.re + 1, c.im)),
-        }
+        self.step(Topology::Bounded, c, d)
     }
 
-    fn min_to_goal(&mut self, start: Complex<usize>, goal: Complex<usize>) -> usize {
+    fn min_to_goal(
+        &mut self,
+        start: Complex<usize>,
+        goal: Complex<usize>,
+        recorder: &mut impl Recorder,
+    ) -> usize {
+        trace_span!("min_to_goal", ?start, ?goal);
         let mut bs = self.bs.clone();
         let mut i = 0;
         while ALL_DS.iter().any(|d| bs.contains(&(start, *d))) {
             self.update_blizzards(&mut bs);
+            recorder.record(|| self.draw(&bs, &HashSet::new()));
             i += 1;
         }
         let mut cs = HashSet::from([start]);
@@ -88,6 +86,8 @@ impl Map {
             self.update_blizzards(&mut bs);
             self.do_legal_moves(&mut cs, &bs);
             cs.insert(start);
+            stat!("day_24.frontier", cs.len());
+            recorder.record(|| self.draw(&bs, &cs));
             i += 1;
             if cs.contains(&goal) {
                 break;
@@ -97,6 +97,29 @@ impl Map {
         i + 1
     }
 
+    /// The valley in the puzzle's notation, with a count where blizzards overlap and an `E`
+    /// wherever an elf could be.
+    fn draw(&self, bs: &HashSet<(Complex<usize>, Dir)>, cs: &HashSet<Complex<usize>>) -> Canvas {
+        let mut valley = Grid {
+            items: vec!['.'; self.w * self.h],
+            w: self.w,
+        };
+        for &(c, d) in bs {
+            let i = valley.xy_to_i((c.re, c.im));
+            valley[i] = match (valley[i], d) {
+                ('.', Dir::U) => '^',
+                ('.', Dir::D) => 'v',
+                ('.', Dir::L) => '<',
+                ('.', Dir::R) => '>',
+                (n @ '2'..='3', _) => (n as u8 + 1) as char,
+                _ => '2',
+            };
+        }
+        let mut canvas = Canvas::from_grid(&valley);
+        canvas.highlight(cs.iter().map(|c| (c.re as isize, c.im as isize)), 'E');
+        canvas
+    }
+
     fn do_legal_moves(
         &self,
         cs: &mut HashSet<Complex<usize>>,
@@ -137,53 +160,76 @@ impl Map {
 }
 
 impl TryFrom<&str> for Map {
-    type Error = String;
+    type Error = ParseError;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
-        let w = s.split_once('\n').unwrap().0.len() - 2;
-        let h = s.lines().count() - 2;
-        let bs = s
-            .lines()
-            .skip(1)
+        let err = |rest: &str, expected: &str| ParseError::at(s, rest, expected).day(24);
+        let lines: Vec<&str> = s.lines().collect();
+        if lines.len() < 3 || lines[0].len() < 3 {
+            return Err(err(
+                &s[s.len()..],
+                "a walled valley at least one tile across",
+            ));
+        }
+        let w = lines[0].len() - 2;
+        let h = lines.len() - 2;
+        for l in lines.iter() {
+            if l.len() != w + 2 {
+                return Err(err(l, &format!("row of width {}", w + 2)));
+            }
+            if let Some(i) = l.find(|c| !"#.^v<>".contains(c)) {
+                return Err(err(&l[i..], "`#`, `.`, `^`, `v`, `<` or `>`"));
+            }
+        }
+        let bs = lines[1..=h]
+            .iter()
             .enumerate()
             .flat_map(|(i, l)| {
-                l.trim_matches('#')
+                l[1..=w]
                     .chars()
                     .enumerate()
-                    .filter_map(move |(j, c)| {
-                        "^v<>"
-                            .contains(c)
-                            .then(|| Dir::try_from(c).map(|d| (complex!(j, i), d)))
-                    })
+                    .filter(|&(_, c)| "^v<>".contains(c))
+                    .map(move |(j, c)| Dir::try_from(c).map(|d| (complex!(j, i), d)))
             })
-            .collect::<Result<HashSet<_>, Self::Error>>()?;
+            .collect::<Result<HashSet<_>, _>>()
+            .map_err(|e| err(s, &e))?;
         Ok(Self { bs, w, h })
     }
 }
 
 #[aoc_generator(day24)]
-pub fn get_input(input: &str) -> Map {
-    input.try_into().unwrap()
+pub fn get_input(input: &str) -> Result<Map, ParseError> {
+    input.try_into()
 }
 
 #[aoc(day24, part1)]
 pub fn part_1(map: &Map) -> usize {
     map.clone()
-        .min_to_goal(complex!(0, 0), complex!(map.w - 1, map.h - 1))
+        .min_to_goal(complex!(0, 0), complex!(map.w - 1, map.h - 1), &mut Discard)
 }
 
 #[aoc(day24, part2)]
 pub fn part_2(map: &Map) -> usize {
     let mut map = map.clone();
     let (start, end) = (complex!(0, 0), complex!(map.w - 1, map.h - 1));
-    let goal_1 = map.min_to_goal(start, end);
+    let goal_1 = map.min_to_goal(start, end, &mut Discard);
     map.step_blizzards();
-    let snack = map.min_to_goal(end, start);
+    let snack = map.min_to_goal(end, start, &mut Discard);
     map.step_blizzards();
-    let goal_2 = map.min_to_goal(start, end);
+    let goal_2 = map.min_to_goal(start, end, &mut Discard);
     goal_1 + snack + goal_2
 }
 
+/// Part 1, one frame per minute.
+pub fn animate(map: &Map, recorder: &mut impl Recorder) {
+    map.clone()
+        .min_to_goal(complex!(0, 0), complex!(map.w - 1, map.h - 1), recorder);
+}
+
+day!(Map, 24, usize, usize);
+
+pub static SOLUTIONS: [runner::Day; 1] = [runner::Day::of::<Map>()];
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -223,11 +269,11 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(&get_input(INPUT)), 18);
+        assert_eq!(part_1(&get_input(INPUT).unwrap()), 18);
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(&get_input(INPUT)), 54);
+        assert_eq!(part_2(&get_input(INPUT).unwrap()), 54);
     }
 }
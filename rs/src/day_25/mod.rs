@@ -1,9 +1,39 @@
+use std::{fmt, str::FromStr};
+
 use num::Integer;
 
+use crate::{error::ParseError, runner};
+
 pub struct Snafu(String);
 
-impl From<&Snafu> for isize {
-    fn from(snafu: &Snafu) -> isize {
+impl TryFrom<&str> for Snafu {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s.find(|c| !"012-=".contains(c)) {
+            Some(i) => Err(ParseError::at(s, &s[i..], "SNAFU digit").day(25)),
+            None if s.is_empty() => Err(ParseError::at(s, s, "SNAFU digit").day(25)),
+            None => Ok(Self(s.to_owned())),
+        }
+    }
+}
+
+impl FromStr for Snafu {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
+impl fmt::Display for Snafu {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<&Snafu> for i64 {
+    fn from(snafu: &Snafu) -> i64 {
         snafu
             .0
             .as_str()
@@ -21,13 +51,17 @@ impl From<&Snafu> for isize {
     }
 }
 
-impl From<isize> for Snafu {
-    fn from(n: isize) -> Self {
+impl From<i64> for Snafu {
+    fn from(n: i64) -> Self {
+        if n == 0 {
+            return Self("0".to_owned());
+        }
         let mut n = n;
         let mut r;
         let mut result: Vec<char> = vec![];
-        while n > 0 {
-            (n, r) = (n + 2).div_rem(&5);
+        // flooring so negative numbers work too, e.g. -3 is 5 × -1 + 2, or `-2`
+        while n != 0 {
+            (n, r) = (n + 2).div_mod_floor(&5);
             result.push(['=', '-', '0', '1', '2'][r as usize]);
         }
         result.reverse();
@@ -36,41 +70,69 @@ impl From<isize> for Snafu {
 }
 
 #[aoc_generator(day25)]
-pub fn get_input(input: &str) -> Vec<Snafu> {
-    input.lines().map(|line| Snafu(line.to_owned())).collect()
+pub fn get_input(input: &str) -> Result<Vec<Snafu>, ParseError> {
+    input
+        .lines()
+        .map(|l| Snafu::try_from(l).map_err(|e| e.within(input, l)))
+        .collect()
 }
 
 #[aoc(day25, part1)]
 pub fn part_1(snafus: &[Snafu]) -> String {
-    Snafu::from(snafus.iter().map(isize::from).sum::<isize>()).0
+    Snafu::from(snafus.iter().map(i64::from).sum::<i64>()).0
 }
 
+/// Each hot air balloon's fuel requirement.
+pub struct FuelRequirements(pub Vec<Snafu>);
+
+day!(FuelRequirements.0, 25, String);
+
+pub static SOLUTIONS: [runner::Day; 1] = [runner::Day::of::<FuelRequirements>()];
+
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     const INPUT: &str = include_str!("../../test_data/day_25.txt");
 
     #[test]
-    fn test_isize_from_snafu() {
-        assert_eq!(isize::from(&Snafu("0".to_owned())), 0);
-        assert_eq!(isize::from(&Snafu("12111".to_owned())), 906);
-        assert_eq!(isize::from(&Snafu("2=0=".to_owned())), 198);
-        assert_eq!(isize::from(&Snafu("21".to_owned())), 11);
-        assert_eq!(isize::from(&Snafu("2=01".to_owned())), 201);
-        assert_eq!(isize::from(&Snafu("111".to_owned())), 31);
-        assert_eq!(isize::from(&Snafu("20012".to_owned())), 1257);
-        assert_eq!(isize::from(&Snafu("112".to_owned())), 32);
-        assert_eq!(isize::from(&Snafu("1=-1=".to_owned())), 353);
-        assert_eq!(isize::from(&Snafu("1-12".to_owned())), 107);
-        assert_eq!(isize::from(&Snafu("12".to_owned())), 7);
-        assert_eq!(isize::from(&Snafu("1=".to_owned())), 3);
-        assert_eq!(isize::from(&Snafu("122".to_owned())), 37);
-        assert_eq!(isize::from(&Snafu("1=-0-2".to_owned())), 1747);
+    fn test_i64_from_snafu() {
+        assert_eq!(i64::from(&Snafu("0".to_owned())), 0);
+        assert_eq!(i64::from(&Snafu("12111".to_owned())), 906);
+        assert_eq!(i64::from(&Snafu("2=0=".to_owned())), 198);
+        assert_eq!(i64::from(&Snafu("21".to_owned())), 11);
+        assert_eq!(i64::from(&Snafu("2=01".to_owned())), 201);
+        assert_eq!(i64::from(&Snafu("111".to_owned())), 31);
+        assert_eq!(i64::from(&Snafu("20012".to_owned())), 1257);
+        assert_eq!(i64::from(&Snafu("112".to_owned())), 32);
+        assert_eq!(i64::from(&Snafu("1=-1=".to_owned())), 353);
+        assert_eq!(i64::from(&Snafu("1-12".to_owned())), 107);
+        assert_eq!(i64::from(&Snafu("12".to_owned())), 7);
+        assert_eq!(i64::from(&Snafu("1=".to_owned())), 3);
+        assert_eq!(i64::from(&Snafu("122".to_owned())), 37);
+        assert_eq!(i64::from(&Snafu("1=-0-2".to_owned())), 1747);
     }
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(&get_input(INPUT)), "2=-1=0");
+        assert_eq!(part_1(&get_input(INPUT).unwrap()), "2=-1=0");
+    }
+
+    proptest! {
+        #[test]
+        fn test_round_trip(n in -(1i64 << 60)..(1i64 << 60)) {
+            let snafu = Snafu::from(n);
+            prop_assert!(Snafu::try_from(snafu.0.as_str()).is_ok());
+            prop_assert!(snafu.0 == "0" || !snafu.0.starts_with('0'));
+            prop_assert_eq!(i64::from(&snafu), n);
+        }
+
+        #[test]
+        fn test_canonical_round_trip(s in "[12=-][012=-]{0,20}") {
+            let n = i64::from(&Snafu(s.clone()));
+            prop_assert_eq!(Snafu::from(n).0, s);
+        }
     }
 }
@@ -0,0 +1,148 @@
+use std::fmt;
+
+use nom::IResult;
+
+/// Error for puzzle input that doesn't parse, pointing at the place in the input where it went
+/// wrong. Lines and columns are 1-indexed, and columns count characters rather than bytes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: Option<u8>,
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, expected: impl Into<String>) -> Self {
+        Self {
+            day: None,
+            line,
+            column,
+            expected: expected.into(),
+        }
+    }
+
+    /// Report an error at the start of `rest`, which must be a slice of `input` (e.g. a line from
+    /// `input.lines()`, or the remaining input handed back by a nom parser).
+    pub fn at(input: &str, rest: &str, expected: impl Into<String>) -> Self {
+        let offset = (rest.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|&o| o <= input.len())
+            .expect("rest is not a slice of input");
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Self::new(
+            before.matches('\n').count() + 1,
+            before[line_start..].chars().count() + 1,
+            expected,
+        )
+    }
+
+    /// Report an error just past the end of `input`.
+    pub fn at_end(input: &str, expected: impl Into<String>) -> Self {
+        Self::at(input, &input[input.len()..], expected)
+    }
+
+    pub fn from_nom(input: &str, e: nom::Err<nom::error::Error<&str>>) -> Self {
+        match e {
+            nom::Err::Incomplete(_) => Self::at_end(input, "more input"),
+            nom::Err::Error(e) | nom::Err::Failure(e) => {
+                Self::at(input, e.input, e.code.description().to_ascii_lowercase())
+            }
+        }
+    }
+
+    /// Shift an error reported relative to `inner` so that it's relative to `outer` instead, where
+    /// `inner` is a slice of `outer`.
+    pub fn within(self, outer: &str, inner: &str) -> Self {
+        let start = Self::at(outer, inner, "");
+        Self {
+            line: start.line + self.line - 1,
+            column: if self.line == 1 {
+                start.column + self.column - 1
+            } else {
+                self.column
+            },
+            ..self
+        }
+    }
+
+    pub fn day(self, day: u8) -> Self {
+        Self {
+            day: Some(day),
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {}, ", day)?;
+        }
+        write!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Unwrap the result of running a nom parser over all of `input`, treating anything other than
+/// trailing whitespace left over as an error.
+pub fn finish<'a, O>(input: &'a str, result: IResult<&'a str, O>) -> Result<O, ParseError> {
+    let (rest, output) = result.map_err(|e| ParseError::from_nom(input, e))?;
+    if rest.trim().is_empty() {
+        Ok(output)
+    } else {
+        Err(ParseError::at(input, rest, "end of input"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_at() {
+        let input = "abc\ndéf\nghi";
+        assert_eq!(
+            ParseError::at(input, input, "x"),
+            ParseError::new(1, 1, "x")
+        );
+        assert_eq!(
+            ParseError::at(input, &input[2..], "x"),
+            ParseError::new(1, 3, "x")
+        );
+        let f = input.find('f').unwrap();
+        assert_eq!(
+            ParseError::at(input, &input[f..], "x"),
+            ParseError::new(2, 3, "x")
+        );
+        assert_eq!(ParseError::at_end(input, "x"), ParseError::new(3, 4, "x"));
+    }
+
+    #[test]
+    fn test_within() {
+        let input = "abc\ndef\nghi";
+        let inner = &input[5..];
+        assert_eq!(
+            ParseError::new(1, 2, "x").within(input, inner),
+            ParseError::new(2, 3, "x")
+        );
+        assert_eq!(
+            ParseError::new(2, 2, "x").within(input, inner),
+            ParseError::new(3, 2, "x")
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            ParseError::new(3, 7, "digit").day(5).to_string(),
+            "day 5, line 3, column 7: expected digit"
+        );
+    }
+}
@@ -6,8 +6,10 @@ use std::{
 
 use num::Integer;
 
+use crate::error::ParseError;
+
 /// Represent a two-dimensional grid in a flat structure.
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct Grid<T> {
     pub items: Vec<T>,
     pub w: usize,
@@ -36,8 +38,60 @@ impl<T, Idx: SliceIndex<[T]>> IndexMut<Idx> for Grid<T> {
     }
 }
 
-#[allow(dead_code)]
+/// The four orthogonal neighbors, clockwise from above.
+pub const ORTHOGONAL: &[(isize, isize)] = &[(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// The four diagonal neighbors, ↖ ↗ ↙ ↘.
+pub const DIAGONAL: &[(isize, isize)] = &[(-1, -1), (1, -1), (-1, 1), (1, 1)];
+
+/// All eight surrounding cells, clockwise from above.
+pub const SURROUNDING: &[(isize, isize)] = &[
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// How the edges of a `w` × `h` area join up, which decides where stepping off of one leads.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Topology {
+    /// Nothing lies past the edges.
+    Bounded,
+    /// Left joins right and top joins bottom.
+    Torus,
+    /// Left joins right, but top and bottom are edges.
+    Cylinder,
+}
+
+impl Topology {
+    /// Where moving from `(x, y)` by `(dx, dy)` ends up, if anywhere.
+    pub fn step(
+        self,
+        (x, y): (usize, usize),
+        (dx, dy): (isize, isize),
+        (w, h): (usize, usize),
+    ) -> Option<(usize, usize)> {
+        let bounded = |a: usize, da: isize, n: usize| a.checked_add_signed(da).filter(|&a| a < n);
+        let wrapped = |a: usize, da: isize, n: usize| {
+            (n > 0).then(|| (a as isize + da).rem_euclid(n as isize) as usize)
+        };
+        match self {
+            Topology::Bounded => Some((bounded(x, dx, w)?, bounded(y, dy, h)?)),
+            Topology::Torus => Some((wrapped(x, dx, w)?, wrapped(y, dy, h)?)),
+            Topology::Cylinder => Some((wrapped(x, dx, w)?, bounded(y, dy, h)?)),
+        }
+    }
+}
+
 impl<T: Clone> Grid<T> {
+    pub fn h(&self) -> usize {
+        self.items.len().checked_div(self.w).unwrap_or(0)
+    }
+
     pub fn xy_to_i(&self, (x, y): (usize, usize)) -> usize {
         x + y * self.w
     }
@@ -52,56 +106,120 @@ impl<T: Clone> Grid<T> {
     }
 
     pub fn get_xy(&self, (x, y): (usize, usize)) -> Option<&T> {
-        self.items.get(self.xy_to_i((x, y)))
+        (x < self.w).then(|| self.items.get(self.xy_to_i((x, y))))?
     }
 
-    pub fn neighbors(&self, i: usize) -> Vec<(usize, T)> {
-        self.neighbor_ixs(i)
-            .into_iter()
-            .map(move |j| (j, self.items[j].clone()))
-            .collect::<Vec<_>>()
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.items.chunks(self.w.max(1))
     }
 
-    pub fn neighbor_ixs(&self, i: usize) -> Vec<usize> {
-        let mut result: Vec<usize> = Vec::new();
-        let x = i % self.w;
-        if i >= self.w {
-            result.push(i - self.w); // ↑
-        }
-        if x < self.w - 1 {
-            result.push(i + 1); // →
-        }
-        if i < self.items.len() - self.w {
-            result.push(i + self.w); // ↓
-        }
-        if x > 0 {
-            result.push(i - 1); // ←
+    pub fn column(
+        &self,
+        x: usize,
+    ) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator + Clone {
+        self.items.iter().skip(x).step_by(self.w.max(1))
+    }
+
+    pub fn columns(
+        &self,
+    ) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T> + ExactSizeIterator + Clone> {
+        (0..self.w).map(|x| self.column(x))
+    }
+
+    pub fn transpose(&self) -> Self {
+        Grid {
+            items: self.columns().flatten().cloned().collect(),
+            w: self.h(),
         }
-        result
     }
 
-    pub fn neighbors_diagonal(&self, i: usize) -> Vec<(usize, T)> {
-        self.neighbor_ixs_diagonal(i)
+    /// Build a grid from rows of differing lengths, padding the short ones out with `fill`.
+    pub fn from_rows(rows: Vec<Vec<T>>, fill: T) -> Self {
+        let w = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let items = rows
             .into_iter()
-            .map(move |j| (j, self.items[j].clone()))
-            .collect::<Vec<_>>()
+            .flat_map(|mut row| {
+                row.resize(w, fill.clone());
+                row
+            })
+            .collect();
+        Grid { items, w }
     }
 
-    pub fn neighbor_ixs_diagonal(&self, i: usize) -> Vec<usize> {
-        let mut result: Vec<usize> = Vec::new();
-        if i > self.w {
-            result.push(i - self.w - 1); // ↖
-        }
-        if i > self.w - 1 {
-            result.push(i - self.w + 1); // ↗
-        }
-        if i < self.items.len() - self.w {
-            result.push(i + self.w - 1); // ↙
-        }
-        if i < self.items.len() - self.w - 1 {
-            result.push(i + self.w + 1); // ↘
-        }
-        result
+    /// Parse a grid with one cell per character, where `cell` turns a character into a cell or
+    /// says what it expected instead. Rows shorter than the widest are padded with `fill` if there
+    /// is one; otherwise all the rows have to be as wide as the first.
+    pub fn parse_with(
+        s: &str,
+        fill: Option<T>,
+        cell: impl Fn(char) -> Result<T, String>,
+    ) -> Result<Self, ParseError> {
+        let first = s
+            .lines()
+            .next()
+            .ok_or_else(|| ParseError::at_end(s, "grid row"))?;
+        let rows = s
+            .lines()
+            .map(|l| {
+                if fill.is_none() && l.chars().count() != first.chars().count() {
+                    let expected = format!("row of width {}", first.chars().count());
+                    return Err(ParseError::at(s, l, expected));
+                }
+                l.char_indices()
+                    .map(|(i, c)| cell(c).map_err(|e| ParseError::at(s, &l[i..], e)))
+                    .collect()
+            })
+            .collect::<Result<Vec<Vec<T>>, _>>()?;
+        Ok(match fill {
+            Some(fill) => Self::from_rows(rows, fill),
+            None => Grid {
+                w: first.chars().count(),
+                items: rows.into_iter().flatten().collect(),
+            },
+        })
+    }
+
+    /// The cells reached from `(x, y)` by each offset in `stencil`, in the stencil's order. Offsets
+    /// that lead off a bounded edge are skipped; on a small enough torus or cylinder, several
+    /// offsets may land on the same cell (or on `(x, y)` itself).
+    pub fn neighbor_xys<'a>(
+        &self,
+        xy: (usize, usize),
+        topology: Topology,
+        stencil: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let size = (self.w, self.h());
+        stencil
+            .iter()
+            .filter_map(move |&d| topology.step(xy, d, size))
+    }
+
+    /// Flat-index version of [`Grid::neighbor_xys`].
+    pub fn neighbor_ixs_in<'a>(
+        &'a self,
+        i: usize,
+        topology: Topology,
+        stencil: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = usize> + 'a {
+        self.neighbor_xys(self.i_to_xy(i), topology, stencil)
+            .map(|xy| self.xy_to_i(xy))
+    }
+
+    pub fn neighbors(&self, i: usize) -> impl Iterator<Item = (usize, T)> + '_ {
+        self.neighbor_ixs(i).map(|j| (j, self.items[j].clone()))
+    }
+
+    pub fn neighbor_ixs(&self, i: usize) -> impl Iterator<Item = usize> + '_ {
+        self.neighbor_ixs_in(i, Topology::Bounded, ORTHOGONAL)
+    }
+
+    pub fn neighbors_diagonal(&self, i: usize) -> impl Iterator<Item = (usize, T)> + '_ {
+        self.neighbor_ixs_diagonal(i)
+            .map(|j| (j, self.items[j].clone()))
+    }
+
+    pub fn neighbor_ixs_diagonal(&self, i: usize) -> impl Iterator<Item = usize> + '_ {
+        self.neighbor_ixs_in(i, Topology::Bounded, DIAGONAL)
     }
 }
 
@@ -114,19 +232,97 @@ impl<T> IntoIterator for Grid<T> {
     }
 }
 
-impl<T: TryFrom<char>> TryFrom<&str> for Grid<T>
+impl<T: Clone + TryFrom<char>> TryFrom<&str> for Grid<T>
 where
     <T as TryFrom<char>>::Error: Debug,
 {
-    type Error = String;
+    type Error = ParseError;
 
+    /// Rows have to all be the same width; see [`Grid::parse_with`] for padding ragged ones.
     fn try_from(s: &str) -> Result<Self, Self::Error> {
-        let w = s.lines().next().ok_or("empty input")?.len();
-        let items = s
-            .lines()
-            .flat_map(|l| l.chars().map(T::try_from))
-            .collect::<Result<Vec<T>, _>>()
-            .map_err(|e| format!("{:?}", e))?;
-        Ok(Grid { items, w })
+        Self::parse_with(s, None, |c| {
+            T::try_from(c).map_err(|e| format!("grid cell ({:?})", e))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(w: usize, h: usize) -> Grid<usize> {
+        Grid {
+            items: (0..w * h).collect(),
+            w,
+        }
+    }
+
+    #[test]
+    fn test_bounded_neighbors() {
+        let g = grid(3, 3);
+        assert_eq!(g.neighbor_ixs(4).collect::<Vec<_>>(), vec![1, 5, 7, 3]);
+        assert_eq!(g.neighbor_ixs(3).collect::<Vec<_>>(), vec![0, 4, 6]);
+        assert_eq!(g.neighbor_ixs_diagonal(3).collect::<Vec<_>>(), vec![1, 7]);
+        assert_eq!(g.neighbor_ixs_diagonal(5).collect::<Vec<_>>(), vec![1, 7]);
+        assert_eq!(g.neighbor_ixs_diagonal(0).collect::<Vec<_>>(), vec![4]);
+        let column = grid(1, 3);
+        assert_eq!(column.neighbor_ixs(1).collect::<Vec<_>>(), vec![0, 2]);
+        assert_eq!(column.neighbor_ixs_diagonal(1).count(), 0);
+        assert_eq!(g.get_xy((3, 0)), None);
+    }
+
+    #[test]
+    fn test_wrapping_neighbors() {
+        let g = grid(4, 3);
+        assert_eq!(
+            g.neighbor_xys((0, 0), Topology::Torus, ORTHOGONAL)
+                .collect::<Vec<_>>(),
+            vec![(0, 2), (1, 0), (0, 1), (3, 0)]
+        );
+        assert_eq!(
+            g.neighbor_xys((0, 0), Topology::Cylinder, DIAGONAL)
+                .collect::<Vec<_>>(),
+            vec![(3, 1), (1, 1)]
+        );
+        assert_eq!(
+            g.neighbor_xys((3, 2), Topology::Torus, SURROUNDING).count(),
+            8
+        );
+        assert_eq!(
+            g.neighbor_ixs_in(0, Topology::Torus, &[(5, -4)])
+                .collect::<Vec<_>>(),
+            vec![g.xy_to_i((1, 2))]
+        );
+        assert_eq!(Topology::Torus.step((0, 0), (1, 0), (0, 0)), None);
+    }
+
+    #[test]
+    fn test_parse() {
+        let g = Grid::<u8>::try_from("ab\ncd").unwrap();
+        assert_eq!((g.w, g.h()), (2, 2));
+        assert_eq!(
+            Grid::<u8>::try_from("ab\ncd\ne"),
+            Err(ParseError::new(3, 1, "row of width 2"))
+        );
+        let digit = |c: char| c.to_digit(10).ok_or_else(|| "digit".to_owned());
+        assert_eq!(
+            Grid::parse_with("12\n3x", None, digit),
+            Err(ParseError::new(2, 2, "digit"))
+        );
+        let padded = Grid::parse_with("1\n234\n56", Some(0), digit).unwrap();
+        assert_eq!(padded.w, 3);
+        assert_eq!(padded.items, vec![1, 0, 0, 2, 3, 4, 5, 6, 0]);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let g = grid(3, 2);
+        assert_eq!(g.rows().collect::<Vec<_>>(), vec![&[0, 1, 2], &[3, 4, 5]]);
+        assert_eq!(g.column(1).copied().collect::<Vec<_>>(), vec![1, 4]);
+        assert_eq!(g.columns().count(), 3);
+        let t = g.transpose();
+        assert_eq!((t.w, t.h()), (2, 3));
+        assert_eq!(t.items, vec![0, 3, 1, 4, 2, 5]);
+        assert_eq!(t.transpose().items, g.items);
     }
 }
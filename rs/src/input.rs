@@ -0,0 +1,248 @@
+use std::{
+    env,
+    error::Error,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// How a directory of cached inputs is laid out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layout {
+    /// `<root>/<year>/day<N>.txt`, as used by cargo-aoc and the Rust solutions.
+    Rust,
+    /// `<root>/day_<NN>`, as used by the Python solutions.
+    Python,
+}
+
+impl Layout {
+    pub fn path(self, root: &Path, year: u16, day: u8) -> PathBuf {
+        match self {
+            Layout::Rust => root.join(year.to_string()).join(format!("day{}.txt", day)),
+            Layout::Python => root.join(format!("day_{:02}", day)),
+        }
+    }
+}
+
+/// Somewhere to get an input from when it isn't cached yet.
+pub trait Fetcher {
+    fn fetch(&self, year: u16, day: u8) -> Result<String, Box<dyn Error>>;
+}
+
+/// Fetches `<base_url>/<year>/day/<day>/input`, logged in with a session cookie.
+pub struct Http {
+    base_url: String,
+    session: String,
+}
+
+impl Http {
+    pub fn new(session: impl Into<String>) -> Self {
+        Self {
+            base_url: DEFAULT_BASE_URL.to_owned(),
+            session: session.into(),
+        }
+    }
+
+    /// Fetch from somewhere other than the Advent of Code site, e.g. a stub server for tests.
+    pub fn base_url(self, base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            ..self
+        }
+    }
+}
+
+impl Fetcher for Http {
+    fn fetch(&self, year: u16, day: u8) -> Result<String, Box<dyn Error>> {
+        let url = format!(
+            "{}/{}/day/{}/input",
+            self.base_url.trim_end_matches('/'),
+            year,
+            day
+        );
+        let response = ureq::get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", "aoc_2022 input fetcher")
+            .call()
+            .map_err(|e| format!("couldn't fetch {}: {}", url, e))?;
+        Ok(response.into_string()?)
+    }
+}
+
+/// Finds a day's input in the first cache that has it, and otherwise fetches it. Whatever it
+/// finds gets written to every cache that was missing it, so the layouts stay in sync.
+pub struct Provider {
+    year: u16,
+    caches: Vec<(PathBuf, Layout)>,
+    fetcher: Option<Box<dyn Fetcher>>,
+}
+
+impl Provider {
+    /// A provider with no caches and nothing to fetch with.
+    pub fn new(year: u16) -> Self {
+        Self {
+            year,
+            caches: Vec::new(),
+            fetcher: None,
+        }
+    }
+
+    /// What the binary uses, from inside `rs/`:
+    /// - `input/` with the Rust layout, and `../py/inputs/` with the Python layout if it exists
+    /// - fetching with the session token in `AOC_SESSION`, or else in `../py/aoc_creds.json`
+    /// - from `AOC_BASE_URL` if that's set
+    pub fn from_env(year: u16) -> Self {
+        let mut provider = Self::new(year).cache("input", Layout::Rust);
+        let py_inputs = Path::new("../py/inputs");
+        if py_inputs.is_dir() {
+            provider = provider.cache(py_inputs, Layout::Python);
+        }
+        if let Some(session) = env::var("AOC_SESSION").ok().or_else(py_session) {
+            let http = Http::new(session);
+            provider = provider.fetcher(match env::var("AOC_BASE_URL") {
+                Ok(base_url) => http.base_url(base_url),
+                Err(_) => http,
+            });
+        }
+        provider
+    }
+
+    pub fn cache(mut self, root: impl Into<PathBuf>, layout: Layout) -> Self {
+        self.caches.push((root.into(), layout));
+        self
+    }
+
+    pub fn fetcher(self, fetcher: impl Fetcher + 'static) -> Self {
+        Self {
+            fetcher: Some(Box::new(fetcher)),
+            ..self
+        }
+    }
+
+    /// Where the first cache keeps this day's input.
+    pub fn path(&self, day: u8) -> Option<PathBuf> {
+        let (root, layout) = self.caches.first()?;
+        Some(layout.path(root, self.year, day))
+    }
+
+    pub fn get(&self, day: u8) -> Result<String, Box<dyn Error>> {
+        let paths: Vec<PathBuf> = self
+            .caches
+            .iter()
+            .map(|(root, layout)| layout.path(root, self.year, day))
+            .collect();
+        let mut missing = Vec::new();
+        let mut input = None;
+        for path in paths.iter() {
+            match fs::read_to_string(path) {
+                Ok(found) => {
+                    input = Some(found);
+                    break;
+                }
+                Err(e) if e.kind() == io::ErrorKind::NotFound => missing.push(path),
+                Err(e) => return Err(format!("couldn't read {}: {}", path.display(), e).into()),
+            }
+        }
+        let input = match (input, &self.fetcher) {
+            (Some(input), _) => input,
+            (None, Some(fetcher)) => fetcher.fetch(self.year, day)?,
+            (None, None) => {
+                let tried: Vec<String> = paths.iter().map(|p| p.display().to_string()).collect();
+                return Err(format!(
+                    "no input for day {} in {} and no session token to fetch it with",
+                    day,
+                    tried.join(" or ")
+                )
+                .into());
+            }
+        };
+        for path in missing {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(path, &input)
+                .map_err(|e| format!("couldn't write {}: {}", path.display(), e))?;
+        }
+        Ok(input)
+    }
+}
+
+/// The session token the Python solutions keep in `aoc_creds.json`.
+fn py_session() -> Option<String> {
+    let creds = fs::read_to_string("../py/aoc_creds.json").ok()?;
+    let creds: serde_json::Value = serde_json::from_str(&creds).ok()?;
+    Some(creds.get("session")?.as_str()?.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc_2022_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_caches() {
+        let dir = temp_dir("caches");
+        let provider = Provider::new(2022)
+            .cache(dir.join("rs"), Layout::Rust)
+            .cache(dir.join("py"), Layout::Python);
+        assert!(provider.get(3).is_err());
+        fs::create_dir_all(dir.join("py")).unwrap();
+        fs::write(dir.join("py/day_03"), "abc\n").unwrap();
+        assert_eq!(provider.get(3).unwrap(), "abc\n");
+        assert_eq!(
+            fs::read_to_string(dir.join("rs/2022/day3.txt")).unwrap(),
+            "abc\n"
+        );
+        assert_eq!(provider.path(3), Some(dir.join("rs/2022/day3.txt")));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_fetch() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            for line in BufReader::new(&stream).lines() {
+                let line = line.unwrap();
+                if line.is_empty() {
+                    break;
+                }
+                request.push(line);
+            }
+            let body = "1000\n2000\n";
+            write!(
+                &stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            request
+        });
+        let dir = temp_dir("fetch");
+        let provider = Provider::new(2022)
+            .cache(&dir, Layout::Python)
+            .fetcher(Http::new("s3cret").base_url(base_url));
+        assert_eq!(provider.get(1).unwrap(), "1000\n2000\n");
+        let request = server.join().unwrap();
+        assert_eq!(request[0], "GET /2022/day/1/input HTTP/1.1");
+        assert!(request.iter().any(|l| l == "Cookie: session=s3cret"));
+        // cached now, so this doesn't go back to the server
+        assert_eq!(provider.get(1).unwrap(), "1000\n2000\n");
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
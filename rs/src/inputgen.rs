@@ -0,0 +1,803 @@
+use std::{collections::HashSet, fmt::Write};
+
+use rand::{seq::SliceRandom, Rng as _, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+type Rng = ChaCha8Rng;
+
+/// Makes random inputs in one day's format. The same seed and size always give the same input,
+/// and inputs grow roughly linearly with `size` (elves, lines, monkeys, valves, grid side...).
+pub struct Generator {
+    pub day: u8,
+    generate: fn(&mut Rng, usize) -> String,
+}
+
+impl Generator {
+    pub fn generate(&self, seed: u64, size: usize) -> String {
+        (self.generate)(&mut Rng::seed_from_u64(seed), size.max(1))
+    }
+}
+
+macro_rules! generator {
+    ($day:literal, $f:ident) => {
+        Generator {
+            day: $day,
+            generate: $f,
+        }
+    };
+}
+
+pub static GENERATORS: [Generator; 25] = [
+    generator!(1, day_01),
+    generator!(2, day_02),
+    generator!(3, day_03),
+    generator!(4, day_04),
+    generator!(5, day_05),
+    generator!(6, day_06),
+    generator!(7, day_07),
+    generator!(8, day_08),
+    generator!(9, day_09),
+    generator!(10, day_10),
+    generator!(11, day_11),
+    generator!(12, day_12),
+    generator!(13, day_13),
+    generator!(14, day_14),
+    generator!(15, day_15),
+    generator!(16, day_16),
+    generator!(17, day_17),
+    generator!(18, day_18),
+    generator!(19, day_19),
+    generator!(20, day_20),
+    generator!(21, day_21),
+    generator!(22, day_22),
+    generator!(23, day_23),
+    generator!(24, day_24),
+    generator!(25, day_25),
+];
+
+pub fn generator(day: u8) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|g| g.day == day)
+}
+
+/// Inputs for `day` as a proptest strategy, from any seed and a size in `sizes`.
+#[cfg(test)]
+pub(crate) fn arbitrary(
+    day: u8,
+    sizes: std::ops::RangeInclusive<usize>,
+) -> impl proptest::strategy::Strategy<Value = String> {
+    use proptest::prelude::*;
+    let generator = generator(day).expect("every day has a generator");
+    (any::<u64>(), sizes).prop_map(move |(seed, size)| generator.generate(seed, size))
+}
+
+const LOWERCASE: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+fn lines(n: usize, mut line: impl FnMut(usize) -> String) -> String {
+    (0..n).map(&mut line).collect::<Vec<_>>().join("\n") + "\n"
+}
+
+/// Calorie counts, one elf per paragraph.
+fn day_01(rng: &mut Rng, size: usize) -> String {
+    let elves: Vec<String> = (0..size)
+        .map(|_| {
+            lines(rng.gen_range(1..=6), |_| {
+                rng.gen_range(1000..=60000).to_string()
+            })
+        })
+        .collect();
+    elves.join("\n")
+}
+
+/// Rounds of rock, paper, scissors.
+fn day_02(rng: &mut Rng, size: usize) -> String {
+    lines(size, |_| {
+        format!(
+            "{} {}",
+            *b"ABC".choose(rng).unwrap() as char,
+            *b"XYZ".choose(rng).unwrap() as char
+        )
+    })
+}
+
+/// Groups of three rucksacks, each with exactly one item in both compartments and exactly one
+/// item (the badge) common to the whole group.
+fn day_03(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size {
+        let mut letters = LETTERS.to_vec();
+        letters.shuffle(rng);
+        let badge = letters.pop().unwrap();
+        // every other letter is left out of one of the rucksacks, so only the badge is in all
+        let pools: Vec<&[u8]> = letters.chunks(letters.len().div_ceil(3)).collect();
+        for r in 0..3 {
+            let mut available: Vec<u8> = (0..3)
+                .filter(|&p| p != r)
+                .flat_map(|p| pools[p].iter().copied())
+                .collect();
+            available.shuffle(rng);
+            let shared = if rng.gen_bool(0.2) {
+                badge
+            } else {
+                available.pop().unwrap()
+            };
+            let (lefts, rights) = available.split_at(available.len() / 2);
+            let half = rng.gen_range(2..=16);
+            let mut left = vec![shared];
+            if shared != badge {
+                left.push(badge);
+            }
+            while left.len() < half.max(2) {
+                left.push(*lefts.choose(rng).unwrap());
+            }
+            let mut right = vec![shared];
+            while right.len() < left.len() {
+                right.push(*rights.choose(rng).unwrap());
+            }
+            left.shuffle(rng);
+            right.shuffle(rng);
+            out.extend(left.into_iter().chain(right).map(char::from));
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Pairs of section ranges.
+fn day_04(rng: &mut Rng, size: usize) -> String {
+    let range = |rng: &mut Rng| {
+        let a = rng.gen_range(1..=99);
+        (a, rng.gen_range(a..=99))
+    };
+    lines(size, |_| {
+        let ((a, b), (c, d)) = (range(rng), range(rng));
+        format!("{}-{},{}-{}", a, b, c, d)
+    })
+}
+
+/// Stacks of crates and `size` moves, none of them taking more crates than there are.
+fn day_05(rng: &mut Rng, size: usize) -> String {
+    let n = rng.gen_range(3..=9);
+    let mut stacks: Vec<Vec<u8>> = (0..n)
+        .map(|_| {
+            (0..rng.gen_range(1..=8))
+                .map(|_| rng.gen_range(b'A'..=b'Z'))
+                .collect()
+        })
+        .collect();
+    let height = stacks.iter().map(Vec::len).max().unwrap();
+    let mut out = String::new();
+    for y in (0..height).rev() {
+        let row: Vec<String> = stacks
+            .iter()
+            .map(|s| match s.get(y) {
+                Some(&c) => format!("[{}]", c as char),
+                None => "   ".to_owned(),
+            })
+            .collect();
+        writeln!(out, "{}", row.join(" ")).unwrap();
+    }
+    let numbers: Vec<String> = (1..=n).map(|i| format!(" {} ", i)).collect();
+    writeln!(out, "{}\n", numbers.join(" ")).unwrap();
+    for _ in 0..size {
+        let nonempty: Vec<usize> = (0..n).filter(|&i| !stacks[i].is_empty()).collect();
+        let from = *nonempty.choose(rng).unwrap();
+        let to = (from + rng.gen_range(1..n)) % n;
+        let count = rng.gen_range(1..=stacks[from].len());
+        let len = stacks[from].len();
+        let moved = stacks[from].split_off(len - count);
+        stacks[to].extend(moved);
+        writeln!(out, "move {} from {} to {}", count, from + 1, to + 1).unwrap();
+    }
+    out
+}
+
+/// A datastream that ends in fourteen different letters, so there's always a marker.
+fn day_06(rng: &mut Rng, size: usize) -> String {
+    let mut out: Vec<u8> = (0..size * 10)
+        .map(|_| *LOWERCASE.choose(rng).unwrap())
+        .collect();
+    out.extend(LOWERCASE.choose_multiple(rng, 14));
+    String::from_utf8(out).unwrap() + "\n"
+}
+
+/// A depth-first `cd`/`ls` transcript of a tree of up to `size` directories.
+fn day_07(rng: &mut Rng, size: usize) -> String {
+    fn walk(rng: &mut Rng, dirs: &mut usize, depth: usize, out: &mut String) {
+        writeln!(out, "$ ls").unwrap();
+        let mut names: Vec<String> = Vec::new();
+        let mut children = Vec::new();
+        for _ in 0..rng.gen_range(1..=6) {
+            let name: String = (0..rng.gen_range(1..=8))
+                .map(|_| *LOWERCASE.choose(rng).unwrap() as char)
+                .collect();
+            if names.contains(&name) {
+                continue;
+            }
+            if *dirs > 0 && depth < 12 && rng.gen_bool(0.4) {
+                *dirs -= 1;
+                writeln!(out, "dir {}", name).unwrap();
+                children.push(name.clone());
+            } else {
+                let ext = ["", ".txt", ".dat", ".lst"].choose(rng).unwrap();
+                writeln!(out, "{} {}{}", rng.gen_range(1000..=300000), name, ext).unwrap();
+            }
+            names.push(name);
+        }
+        for child in children {
+            writeln!(out, "$ cd {}", child).unwrap();
+            walk(rng, dirs, depth + 1, out);
+            writeln!(out, "$ cd ..").unwrap();
+        }
+    }
+    let mut out = "$ cd /\n".to_owned();
+    walk(rng, &mut size.clone(), 0, &mut out);
+    out
+}
+
+/// A square grid of tree heights.
+fn day_08(rng: &mut Rng, size: usize) -> String {
+    lines(size, |_| {
+        (0..size)
+            .map(|_| char::from(b'0' + rng.gen_range(0..=9)))
+            .collect()
+    })
+}
+
+/// Head motions.
+fn day_09(rng: &mut Rng, size: usize) -> String {
+    lines(size, |_| {
+        format!(
+            "{} {}",
+            *b"LRUD".choose(rng).unwrap() as char,
+            rng.gen_range(1..=20)
+        )
+    })
+}
+
+/// At least 240 cycles' worth of instructions, so both parts have a full screen.
+fn day_10(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+    let mut cycles = 0;
+    while cycles < 240 + size {
+        if rng.gen_bool(0.3) {
+            out.push_str("noop\n");
+            cycles += 1;
+        } else {
+            writeln!(out, "addx {}", rng.gen_range(-20..=20)).unwrap();
+            cycles += 2;
+        }
+    }
+    out
+}
+
+const PRIMES: [usize; 16] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53];
+
+/// Between 2 and 16 monkeys, each with a prime to test divisibility by. They're all different
+/// until their product would reach 2^32, past which part 2's worry levels, squared, overflow a
+/// `u64`, and then the rest reuse them.
+fn day_11(rng: &mut Rng, size: usize) -> String {
+    let n = size.clamp(2, PRIMES.len());
+    let mut primes = PRIMES;
+    primes.shuffle(rng);
+    let mut product = 1;
+    let distinct = primes
+        .iter()
+        .take_while(|&&p| {
+            product *= p as u64;
+            product < 1 << 32
+        })
+        .count();
+    for i in distinct..n {
+        primes[i] = primes[rng.gen_range(0..distinct)];
+    }
+    let monkeys: Vec<String> = (0..n)
+        .map(|i| {
+            let items: Vec<String> = (0..rng.gen_range(1..=5))
+                .map(|_| rng.gen_range(50..=99).to_string())
+                .collect();
+            let operation = match rng.gen_range(0..3) {
+                0 => "old * old".to_owned(),
+                1 => format!("old * {}", rng.gen_range(2..=19)),
+                _ => format!("old + {}", rng.gen_range(1..=8)),
+            };
+            let others: Vec<usize> = (0..n).filter(|&j| j != i).collect();
+            let targets: Vec<&usize> = if n > 2 {
+                others.choose_multiple(rng, 2).collect()
+            } else {
+                vec![&others[0], &others[0]]
+            };
+            format!(
+                "Monkey {}:\n  Starting items: {}\n  Operation: new = {}\n  Test: divisible by {}\n    If true: throw to monkey {}\n    If false: throw to monkey {}\n",
+                i,
+                items.join(", "),
+                operation,
+                primes[i],
+                targets[0],
+                targets[1]
+            )
+        })
+        .collect();
+    monkeys.join("\n")
+}
+
+/// A heightmap that rises from `S` in the top-left to `E` in the bottom-right, with random dips
+/// everywhere but the top row and right column, so there's always a way along those. It's at
+/// least 25 wide, for enough steps to climb to the `y` next to `E` one letter at a time.
+fn day_12(rng: &mut Rng, size: usize) -> String {
+    let (w, h) = ((size * 3 + 2).max(25), size + 2);
+    let span = w + h - 2;
+    lines(h, |y| {
+        (0..w)
+            .map(|x| {
+                if (x, y) == (0, 0) {
+                    return 'S';
+                }
+                if (x, y) == (w - 1, h - 1) {
+                    return 'E';
+                }
+                // rescale x + y onto a..z so neighbors differ by at most one
+                let height = (x + y) * 25 / span;
+                let dip = if height > 0 && y > 0 && x < w - 1 && rng.gen_bool(0.2) {
+                    1
+                } else {
+                    0
+                };
+                char::from(b'a' + (height - dip) as u8)
+            })
+            .collect()
+    })
+}
+
+/// Pairs of nested packets.
+fn day_13(rng: &mut Rng, size: usize) -> String {
+    fn packet(rng: &mut Rng, depth: usize) -> String {
+        let items: Vec<String> = (0..rng.gen_range(0..=5))
+            .map(|_| {
+                if depth < 4 && rng.gen_bool(0.3) {
+                    packet(rng, depth + 1)
+                } else {
+                    rng.gen_range(0..=10).to_string()
+                }
+            })
+            .collect();
+        format!("[{}]", items.join(","))
+    }
+    let pairs: Vec<String> = (0..size)
+        .map(|_| format!("{}\n{}\n", packet(rng, 0), packet(rng, 0)))
+        .collect();
+    pairs.join("\n")
+}
+
+/// Rock paths of horizontal and vertical lines below the sand source at 500,0.
+fn day_14(rng: &mut Rng, size: usize) -> String {
+    let spread = 10 + size;
+    lines(size, |_| {
+        let mut x = rng.gen_range(500usize.saturating_sub(spread)..=500 + spread);
+        let mut y = rng.gen_range(2..=10 + size);
+        let mut points = vec![format!("{},{}", x, y)];
+        for i in 0..rng.gen_range(1..=4) {
+            if i % 2 == 0 {
+                x = rng.gen_range(x.saturating_sub(6)..=x + 6);
+            } else {
+                y = rng.gen_range(y.saturating_sub(6).max(2)..=y + 6);
+            }
+            points.push(format!("{},{}", x, y));
+        }
+        points.join(" -> ")
+    })
+}
+
+/// Sensors and their closest beacons, leaving just the one position in the area part 2 searches
+/// out of range of them all. Four far-reaching sensors cover everything but that gap, and `size`
+/// more are spread over the area, each stopping short of it.
+fn day_15(rng: &mut Rng, size: usize) -> String {
+    let (gx, gy): (isize, isize) = (rng.gen_range(0..=4000000), rng.gen_range(0..=4000000));
+    let mut sensors = Vec::new();
+    // each covers one side of the gap's diagonals, x + y = gx + gy and x - y = gx - gy, and
+    // comes within one of it; an odd range keeps them on whole positions
+    for (du, dv) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+        let r: isize = rng.gen_range(4000000..8000000) * 2 + 1;
+        let (u, v) = (gx + gy + du * (r + 1), gx - gy + dv * (r + 1));
+        sensors.push(((u + v) / 2, (u - v) / 2, r));
+    }
+    while sensors.len() < size + 4 {
+        let (sx, sy): (isize, isize) = (rng.gen_range(0..=4000000), rng.gen_range(0..=4000000));
+        let gap = (sx - gx).abs() + (sy - gy).abs();
+        if gap > 1 {
+            sensors.push((sx, sy, rng.gen_range(1..gap.min(1000000))));
+        }
+    }
+    sensors.shuffle(rng);
+    lines(sensors.len(), |i| {
+        let (sx, sy, r) = sensors[i];
+        let dx = rng.gen_range(-r..=r);
+        let dy = (r - dx.abs()) * if rng.gen_bool(0.5) { 1 } else { -1 };
+        format!(
+            "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+            sx,
+            sy,
+            sx + dx,
+            sy + dy
+        )
+    })
+}
+
+/// A connected graph of `size` valves (at most 26 × 26), starting from `AA`, where about half the
+/// valves have a flow rate.
+fn day_16(rng: &mut Rng, size: usize) -> String {
+    let n = size.clamp(2, 26 * 26);
+    let mut names: Vec<String> = (b'A'..=b'Z')
+        .flat_map(|a| (b'A'..=b'Z').map(move |b| String::from_utf8(vec![a, b]).unwrap()))
+        .filter(|name| name != "AA")
+        .collect();
+    names.shuffle(rng);
+    names.truncate(n - 1);
+    names.insert(0, "AA".to_owned());
+    let mut tunnels: Vec<HashSet<usize>> = vec![HashSet::new(); n];
+    let mut connect = |a: usize, b: usize| {
+        if a != b {
+            tunnels[a].insert(b);
+            tunnels[b].insert(a);
+        }
+    };
+    for i in 1..n {
+        connect(i, rng.gen_range(0..i));
+    }
+    for _ in 0..n / 2 {
+        connect(rng.gen_range(0..n), rng.gen_range(0..n));
+    }
+    lines(n, |i| {
+        let rate = if i > 0 && rng.gen_bool(0.5) {
+            rng.gen_range(1..=25)
+        } else {
+            0
+        };
+        let mut to: Vec<usize> = tunnels[i].iter().copied().collect();
+        to.sort();
+        let to: Vec<&str> = to.iter().map(|&j| names[j].as_str()).collect();
+        let (verb, noun) = if to.len() == 1 {
+            ("tunnel leads", "valve")
+        } else {
+            ("tunnels lead", "valves")
+        };
+        format!(
+            "Valve {} has flow rate={}; {} to {} {}",
+            names[i],
+            rate,
+            verb,
+            noun,
+            to.join(", ")
+        )
+    })
+}
+
+/// Jets of hot gas.
+fn day_17(rng: &mut Rng, size: usize) -> String {
+    let jets: String = (0..size * 10)
+        .map(|_| if rng.gen_bool(0.5) { '<' } else { '>' })
+        .collect();
+    jets + "\n"
+}
+
+/// Lava droplets in a cube that grows with the number of them.
+fn day_18(rng: &mut Rng, size: usize) -> String {
+    let side = (size as f64).cbrt().ceil() as isize * 2 + 2;
+    lines(size, |_| {
+        let mut c = || rng.gen_range(0..side);
+        format!("{},{},{}", c(), c(), c())
+    })
+}
+
+/// Blueprints with costs in the same ranges as real inputs.
+fn day_19(rng: &mut Rng, size: usize) -> String {
+    lines(size, |i| {
+        format!(
+            "Blueprint {}: Each ore robot costs {} ore. Each clay robot costs {} ore. Each obsidian robot costs {} ore and {} clay. Each geode robot costs {} ore and {} obsidian.",
+            i + 1,
+            rng.gen_range(2..=4),
+            rng.gen_range(2..=4),
+            rng.gen_range(2..=4),
+            rng.gen_range(5..=20),
+            rng.gen_range(2..=4),
+            rng.gen_range(5..=20)
+        )
+    })
+}
+
+/// Numbers to mix, with exactly one 0.
+fn day_20(rng: &mut Rng, size: usize) -> String {
+    let mut ns: Vec<isize> = (0..size)
+        .map(|_| {
+            let n = rng.gen_range(1..=10000);
+            if rng.gen_bool(0.5) {
+                n
+            } else {
+                -n
+            }
+        })
+        .collect();
+    ns.insert(rng.gen_range(0..=ns.len()), 0);
+    lines(ns.len(), |i| ns[i].to_string())
+}
+
+/// A tree of about `size` monkeys, built top-down from the value each one has to yell so every
+/// division comes out exact. `humn` is somewhere under the left side of `root`, never as a
+/// divisor, and both sides of `root` are equal, so what `humn` yells is an answer to part 2.
+fn day_21(rng: &mut Rng, size: usize) -> String {
+    struct Tree<'a> {
+        rng: &'a mut Rng,
+        names: HashSet<String>,
+        jobs: Vec<String>,
+    }
+
+    impl Tree<'_> {
+        fn name(&mut self) -> String {
+            loop {
+                let name: String = (0..4)
+                    .map(|_| *LOWERCASE.choose(self.rng).unwrap() as char)
+                    .collect();
+                if name != "root" && name != "humn" && self.names.insert(name.clone()) {
+                    return name;
+                }
+            }
+        }
+
+        /// Monkeys yelling `value` in total, returning the name of the top one.
+        fn grow(&mut self, value: i64, size: usize, humn: bool) -> String {
+            let name = if humn && size <= 1 {
+                "humn".to_owned()
+            } else {
+                self.name()
+            };
+            if size <= 1 {
+                self.jobs.push(format!("{}: {}", name, value));
+                return name;
+            }
+            let left_size = self.rng.gen_range(1..size);
+            let (humn_left, humn_right) = match humn {
+                false => (false, false),
+                true if self.rng.gen_bool(0.5) => (true, false),
+                true => (false, true),
+            };
+            let small = self.rng.gen_range(1..=20);
+            // every monkey yells a positive number, since the format has no negative ones
+            let (op, a, b) = match self.rng.gen_range(0..4) {
+                0 if value > small => ('+', value - small, small),
+                2 if value % small == 0 => ('*', value / small, small),
+                3 if value < 1_000_000_000 => ('/', value * small, small),
+                _ => ('-', value + small, small),
+            };
+            let (a, b) = if self.rng.gen_bool(0.5) && op != '/' && op != '-' {
+                (b, a)
+            } else {
+                (a, b)
+            };
+            // humn is never a divisor, since part 2 couldn't undo that exactly
+            let (humn_left, humn_right) = if op == '/' {
+                (humn, false)
+            } else {
+                (humn_left, humn_right)
+            };
+            let left = self.grow(a, left_size, humn_left);
+            let right = self.grow(b, size - left_size, humn_right);
+            self.jobs
+                .push(format!("{}: {} {} {}", name, left, op, right));
+            name
+        }
+    }
+
+    let mut tree = Tree {
+        rng,
+        names: HashSet::new(),
+        jobs: Vec::new(),
+    };
+    let value = tree.rng.gen_range(1..=1000);
+    let half = (size / 2).max(1);
+    let left = tree.grow(value, half, true);
+    let right = tree.grow(value, half, false);
+    let op = *['+', '-', '*', '/'].choose(tree.rng).unwrap();
+    tree.jobs.push(format!("root: {} {} {}", left, op, right));
+    tree.jobs.shuffle(tree.rng);
+    lines(tree.jobs.len(), |i| tree.jobs[i].clone())
+}
+
+/// The eleven nets of a cube, as which cells of a grid of faces are used.
+const CUBE_NETS: [&[&str]; 11] = [
+    &["#...", "####", "#..."],
+    &["#...", "####", ".#.."],
+    &["#...", "####", "..#."],
+    &["#...", "####", "...#"],
+    &[".#..", "####", ".#.."],
+    &[".#..", "####", "..#."],
+    &["##..", ".###", ".#.."],
+    &["##..", ".###", "..#."],
+    &["##..", ".###", "...#"],
+    &["##..", ".##.", "..##"],
+    &["###..", "..###"],
+];
+
+/// A cube net, flipped and turned at random, with faces `size` tiles across and `size` × 4
+/// instructions to follow.
+fn day_22(rng: &mut Rng, size: usize) -> String {
+    let net: Vec<Vec<bool>> = CUBE_NETS
+        .choose(rng)
+        .unwrap()
+        .iter()
+        .map(|row| row.bytes().map(|b| b == b'#').collect())
+        .collect();
+    let mut net = if rng.gen_bool(0.5) {
+        (0..net[0].len())
+            .map(|x| net.iter().map(|row| row[x]).collect())
+            .collect()
+    } else {
+        net
+    };
+    if rng.gen_bool(0.5) {
+        net.reverse();
+    }
+    if rng.gen_bool(0.5) {
+        net.iter_mut().for_each(|row| row.reverse());
+    }
+    let mut out = String::new();
+    let mut start = true;
+    for row in net.iter() {
+        let used = row.iter().rposition(|&f| f).unwrap() + 1;
+        for _ in 0..size {
+            for &face in row[..used].iter() {
+                for _ in 0..size {
+                    out.push(match face {
+                        false => ' ',
+                        // the first open tile on the top row is where the path starts
+                        true if start || !rng.gen_bool(0.15) => {
+                            start = false;
+                            '.'
+                        }
+                        true => '#',
+                    });
+                }
+            }
+            out.push('\n');
+        }
+    }
+    out.push('\n');
+    for i in 0..size * 4 {
+        if i > 0 {
+            out.push(*['L', 'R'].choose(rng).unwrap());
+        }
+        write!(out, "{}", rng.gen_range(1..=size * 2)).unwrap();
+    }
+    out + "\n"
+}
+
+/// A square grove with about a third of it covered in elves, and at least one.
+fn day_23(rng: &mut Rng, size: usize) -> String {
+    let mut grove: Vec<bool> = (0..size * size).map(|_| rng.gen_bool(0.3)).collect();
+    if !grove.contains(&true) {
+        grove[rng.gen_range(0..size * size)] = true;
+    }
+    lines(size, |y| {
+        grove[y * size..(y + 1) * size]
+            .iter()
+            .map(|&elf| if elf { '#' } else { '.' })
+            .collect()
+    })
+}
+
+/// A walled valley `size` tiles high and three times as wide, with no up or down blizzards in
+/// the columns of the entrance and exit, same as the real ones. The blizzards come back to where
+/// they started every `w` minutes, so there's always a way across and back: a staircase each
+/// way, set off on at a multiple of that, with any blizzard that would get in its way taken out.
+fn day_24(rng: &mut Rng, size: usize) -> String {
+    let (w, h) = (size * 3, size);
+    let mut valley: Vec<Vec<u8>> = (0..h)
+        .map(|_| {
+            (0..w)
+                .map(|x| {
+                    let dirs: &[u8] = if x == 0 || x == w - 1 { b"<>" } else { b"^v<>" };
+                    match rng.gen_bool(0.4) {
+                        true => *dirs.choose(rng).unwrap(),
+                        false => b'.',
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    for ((mut x, mut y), goal) in [((0, 0), (w - 1, h - 1)), ((w - 1, h - 1), (0, 0))] {
+        for t in 0.. {
+            // wherever each kind of blizzard would have started to be here at minute `t`
+            for (bx, by, b) in [
+                ((x + w - t % w) % w, y, b'>'),
+                ((x + t) % w, y, b'<'),
+                (x, (y + h - t % h) % h, b'v'),
+                (x, (y + t) % h, b'^'),
+            ] {
+                if valley[by][bx] == b {
+                    valley[by][bx] = b'.';
+                }
+            }
+            let step = |a: usize, b: usize| if a < b { a + 1 } else { a - 1 };
+            match (x != goal.0, y != goal.1) {
+                (false, false) => break,
+                (true, true) if rng.gen_bool(0.5) => y = step(y, goal.1),
+                (true, _) => x = step(x, goal.0),
+                (false, true) => y = step(y, goal.1),
+            }
+        }
+    }
+    let mut out = format!("#.{}\n", "#".repeat(w));
+    for row in valley {
+        writeln!(out, "#{}#", String::from_utf8(row).unwrap()).unwrap();
+    }
+    writeln!(out, "{}.#", "#".repeat(w)).unwrap();
+    out
+}
+
+/// SNAFU numbers of up to twenty digits.
+fn day_25(rng: &mut Rng, size: usize) -> String {
+    lines(size, |_| {
+        let digits = rng.gen_range(1..=20);
+        let mut n: i64 = rng.gen_range(1..=5i64.pow(digits));
+        let mut digits = Vec::with_capacity(digits as usize + 1);
+        while n > 0 {
+            let (digit, carry) = match n % 5 {
+                3 => ('=', 1),
+                4 => ('-', 1),
+                d => (char::from(b'0' + d as u8), 0),
+            };
+            digits.push(digit);
+            n = n / 5 + carry;
+        }
+        digits.iter().rev().collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner;
+
+    /// Solve every part of `day`'s input from `seed` and `size`, showing the input if it fails.
+    fn solve(day: u8, seed: u64, size: usize) {
+        let input = generator(day).unwrap().generate(seed, size);
+        let solution = runner::day(day).unwrap();
+        match std::panic::catch_unwind(|| solution.run(&input, None)) {
+            Ok(Ok(runs)) => assert_eq!(runs.len(), usize::from(solution.parts)),
+            Ok(Err(e)) => panic!("day {} seed {} size {}: {}\n{}", day, seed, size, e, input),
+            Err(_) => panic!(
+                "day {} seed {} size {} panicked on\n{}",
+                day, seed, size, input
+            ),
+        }
+    }
+
+    #[test]
+    fn test_solves() {
+        for g in GENERATORS.iter() {
+            for (seed, size) in [(0, 1), (1, 5)] {
+                solve(g.day, seed, size);
+            }
+            let input = g.generate(2, 30);
+            if let Err(e) = runner::day(g.day).unwrap().generate(&input) {
+                panic!("day {} seed 2 size 30: {}\n{}", g.day, e, input);
+            }
+        }
+    }
+
+    #[test]
+    #[ignore] // slow
+    fn test_solves_large() {
+        for g in GENERATORS.iter() {
+            solve(g.day, 2, 30);
+        }
+    }
+
+    #[test]
+    fn test_seeded() {
+        let g = generator(16).unwrap();
+        assert_eq!(g.generate(7, 20), g.generate(7, 20));
+        assert_ne!(g.generate(7, 20), g.generate(8, 20));
+        assert!(g.generate(7, 40).lines().count() > g.generate(7, 20).lines().count());
+        assert!(generator(26).is_none());
+    }
+}
@@ -0,0 +1,250 @@
+use std::{
+    iter::Sum,
+    ops::{Range, RangeInclusive, Sub},
+};
+
+/// Types with a next value, so that a closed interval `a..=b` can be stored as the half-open
+/// `a..b + 1`.
+pub trait Discrete: Ord + Copy {
+    /// `None` for the type's maximum.
+    fn next(self) -> Option<Self>;
+    fn prev(self) -> Self;
+}
+
+macro_rules! discrete {
+    ($($t:ty),*) => {
+        $(impl Discrete for $t {
+            fn next(self) -> Option<Self> {
+                self.checked_add(1)
+            }
+
+            fn prev(self) -> Self {
+                self - 1
+            }
+        })*
+    };
+}
+
+discrete!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// A set of values made of intervals. They're kept half-open, sorted and merged, so that no two
+/// overlap or touch: inserting `1..3` and `3..5` gives `1..5`. Closed intervals go in and come
+/// out through the `_closed` methods, which need [`Discrete`] values.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<(T, T)>,
+}
+
+impl<T: Ord + Copy> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Copy> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Add `range`, merging it with any intervals it overlaps or touches. Empty ranges are
+    /// ignored.
+    pub fn insert(&mut self, range: Range<T>) {
+        let (mut start, mut end) = (range.start, range.end);
+        if start >= end {
+            return;
+        }
+        let i = self.intervals.partition_point(|&(_, e)| e < start);
+        let j = self.intervals.partition_point(|&(s, _)| s <= end);
+        if i < j {
+            start = start.min(self.intervals[i].0);
+            end = end.max(self.intervals[j - 1].1);
+        }
+        self.intervals.splice(i..j, [(start, end)]);
+    }
+
+    /// Add every interval of `other`.
+    pub fn merge(&mut self, other: &Self) {
+        for &(s, e) in &other.intervals {
+            self.insert(s..e);
+        }
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        union.merge(other);
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (mut i, mut j) = (0, 0);
+        let mut intervals = Vec::new();
+        while let (Some(&(a, b)), Some(&(c, d))) = (self.intervals.get(i), other.intervals.get(j)) {
+            let (start, end) = (a.max(c), b.min(d));
+            if start < end {
+                intervals.push((start, end));
+            }
+            if b < d {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { intervals }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let mut j = 0;
+        for &(mut start, end) in &self.intervals {
+            // skip what ends before this interval, which can't matter to later ones either
+            while other.intervals.get(j).is_some_and(|&(_, e)| e <= start) {
+                j += 1;
+            }
+            let mut k = j;
+            while let Some(&(s, e)) = other.intervals.get(k).filter(|&&(s, _)| s < end) {
+                if start < s {
+                    intervals.push((start, s));
+                }
+                start = start.max(e);
+                k += 1;
+            }
+            if start < end {
+                intervals.push((start, end));
+            }
+        }
+        Self { intervals }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.intervals.partition_point(|&(_, e)| e <= value);
+        self.intervals.get(i).is_some_and(|&(s, _)| s <= value)
+    }
+
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.difference(self).is_empty()
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    /// The intervals, half-open, in order.
+    pub fn iter(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.intervals.iter().map(|&(s, e)| s..e)
+    }
+
+    /// What's missing between the first interval and the last, half-open, in order.
+    pub fn gaps(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.intervals.windows(2).map(|w| w[0].1..w[1].0)
+    }
+
+    /// How much the intervals cover between them, e.g. how many integers, since a half-open
+    /// interval's length is just `end - start`.
+    pub fn len(&self) -> T
+    where
+        T: Sub<Output = T> + Sum,
+    {
+        self.intervals.iter().map(|&(s, e)| e - s).sum()
+    }
+}
+
+impl<T: Discrete> IntervalSet<T> {
+    /// Add the closed interval `range`, i.e. including its end. Panics if that end is `T`'s
+    /// maximum, since there's nothing for the half-open interval to end at.
+    pub fn insert_closed(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+        if start <= end {
+            let end = end.next().expect("closed interval ending at the maximum");
+            self.insert(start..end);
+        }
+    }
+
+    /// The intervals, closed, in order.
+    pub fn iter_closed(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.intervals.iter().map(|&(s, e)| s..=e.prev())
+    }
+
+    /// What's missing between the first interval and the last, closed, in order.
+    pub fn gaps_closed(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.gaps().map(|gap| gap.start..=gap.end.prev())
+    }
+}
+
+impl<T: Ord + Copy> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+impl<T: Discrete> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert_closed(range);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[Range<i32>]) -> IntervalSet<i32> {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut s = set(&[5..7, 1..3]);
+        assert_eq!(s.iter().collect::<Vec<_>>(), vec![1..3, 5..7]);
+        s.insert(3..4);
+        s.insert(9..9);
+        assert_eq!(s.iter().collect::<Vec<_>>(), vec![1..4, 5..7]);
+        s.insert(0..6);
+        assert_eq!(s, std::iter::once(0..7).collect());
+        assert!(s.contains(0) && s.contains(6) && !s.contains(7));
+    }
+
+    #[test]
+    fn test_closed() {
+        // 1..=3 and 4..=5 cover every integer from 1 to 5, unlike 1..3 and 4..5
+        let closed: IntervalSet<i32> = [1..=3, 4..=5].into_iter().collect();
+        assert_eq!(closed.iter_closed().collect::<Vec<_>>(), vec![1..=5]);
+        assert_eq!(closed.len(), 5);
+        let half_open = set(&[1..3, 4..5]);
+        assert_eq!(half_open.len(), 3);
+        assert_eq!(half_open.gaps().collect::<Vec<_>>(), vec![3..4]);
+        assert_eq!(half_open.gaps_closed().collect::<Vec<_>>(), vec![3..=3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "ending at the maximum")]
+    fn test_closed_at_max() {
+        IntervalSet::new().insert_closed(0..=u8::MAX);
+    }
+
+    #[test]
+    fn test_operations() {
+        let a = set(&[0..4, 6..10, 12..14]);
+        let b = set(&[2..7, 9..13]);
+        assert_eq!(a.union(&b), std::iter::once(0..14).collect());
+        assert_eq!(a.intersection(&b), set(&[2..4, 6..7, 9..10, 12..13]));
+        assert_eq!(a.difference(&b), set(&[0..2, 7..9, 13..14]));
+        assert_eq!(b.difference(&a), set(&[4..6, 10..12]));
+        assert!(a.overlaps(&b));
+        assert!(!a.is_superset(&b));
+        assert!(a.is_superset(&set(&[1..3, 7..8])));
+        assert!(a.is_superset(&IntervalSet::new()));
+    }
+}
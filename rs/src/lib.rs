@@ -1,40 +1,56 @@
-#![feature(array_zip)]
-#![feature(int_log)]
-#![feature(int_roundings)]
-#![feature(iter_intersperse)]
-#![feature(stmt_expr_attributes)]
+#![cfg_attr(feature = "nightly", feature(iter_intersperse))]
 
 #[macro_use]
 extern crate aoc_runner_derive;
 
+pub mod bench;
 #[macro_use]
 mod complex;
-mod grid;
+pub mod error;
+pub mod grid;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod input;
+pub mod inputgen;
+pub mod interval;
+#[cfg(feature = "python")]
+mod python;
+pub mod record;
+pub mod render;
+pub mod report;
+pub mod runner;
+pub mod search;
+#[macro_use]
+pub mod solution;
+#[macro_use]
+pub mod stats;
+pub mod verify;
+#[cfg(feature = "wasm")]
+mod wasm;
 
-mod day_01;
-mod day_02;
-mod day_03;
-mod day_04;
-mod day_05;
-mod day_06;
-mod day_07;
-mod day_08;
-mod day_09;
-mod day_10;
-mod day_11;
-mod day_12;
-mod day_13;
-mod day_14;
-mod day_15;
-mod day_16;
-mod day_17;
-mod day_18;
-mod day_19;
-mod day_20;
-mod day_21;
-mod day_22;
-mod day_23;
-mod day_24;
-mod day_25;
+pub mod day_01;
+pub mod day_02;
+pub mod day_03;
+pub mod day_04;
+pub mod day_05;
+pub mod day_06;
+pub mod day_07;
+pub mod day_08;
+pub mod day_09;
+pub mod day_10;
+pub mod day_11;
+pub mod day_12;
+pub mod day_13;
+pub mod day_14;
+pub mod day_15;
+pub mod day_16;
+pub mod day_17;
+pub mod day_18;
+pub mod day_19;
+pub mod day_20;
+pub mod day_21;
+pub mod day_22;
+pub mod day_23;
+pub mod day_24;
+pub mod day_25;
 
 aoc_lib! { year = 2022 }
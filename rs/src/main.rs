@@ -0,0 +1,553 @@
+use std::{
+    env,
+    error::Error,
+    fs,
+    io::{self, Read, Write},
+    process::ExitCode,
+    str::FromStr,
+    time::Duration,
+};
+
+use aoc_2022::{
+    bench::{self, Baseline, Measurement},
+    input::Provider,
+    inputgen::{self, Generator},
+    record::{self, Animation, Frames},
+    report::{Format, Reporter},
+    runner::{self, Day},
+    stats::Exporter,
+    verify::{self, Registry, DEFAULT_ANSWERS_PATH},
+};
+
+const USAGE: &str = "\
+usage: aoc_2022 run (<day> [part] [--impl <name>] [--input <path>] | --all)
+                    [--format <text|json|csv>] [--stats <path>]
+       aoc_2022 report (<day> [part] [--impl <name>] [--input <path>] | --all)
+                       [--format <json|csv>] [--stats <path>]
+       aoc_2022 verify [<day> [--impl <name>] [--input <path>]] [--answers <path>]
+       aoc_2022 check (<day> [--input <path>] | --all)
+       aoc_2022 bench (<day> [part] [--impl <name>] [--input <path>] | --all) [--samples <n>]
+                      [--budget <secs>] [--baseline <path>] [--save <path>] [--threshold <pct>]
+       aoc_2022 generate <day> [--seed <n>] [--size <n>]
+       aoc_2022 animate <day> [--input <path>] [--every <n>] [--limit <n>] [--gif <path>]
+       aoc_2022 list
+
+Inputs are read from input/2022/day<N>.txt (or ../py/inputs/day_<NN>) unless --input is given;
+use `--input -` to read from stdin. Missing inputs are downloaded using the session token in
+$AOC_SESSION (or ../py/aoc_creds.json), from $AOC_BASE_URL if set, and saved to both. `report`
+is `run` with JSON Lines output by default. `verify` checks the answers against answers.csv (or
+--answers) and fails if any of them have changed. `check` solves each part with every
+implementation there is of it and fails if they don't all agree; it needs no answers, so it
+works on generated inputs too. `bench` times the generator and each part over up to --samples
+runs (default 20, and at most --budget seconds per part, default 5), saves the timings with
+--save, and fails if any median is more than --threshold percent (default 10) slower than in
+--baseline. `generate` prints a random input for a day (size 10 and seed 0 unless given).
+`animate` prints every nth frame of a day's simulation (days 9, 14, 17, 23 and 24), or writes
+them to an animated GIF.
+
+With the `tracing` feature, `--stats` writes what each part's solver counted on the way (states
+searched, frontier sizes and the like) to a file as JSON Lines, and solvers open `tracing` spans
+for any subscriber to pick up.
+
+Days with more than one implementation (see `list`) use the default unless --impl names another.
+`--all` sticks to the defaults, except for `verify` and `check`, which go through every
+implementation, including those that only solve one part.";
+
+/// Pixels per cell and hundredths of a second per frame in animated GIFs.
+const GIF_SCALE: usize = 4;
+const GIF_DELAY: u16 = 5;
+
+enum Selection {
+    One {
+        day: &'static Day,
+        part: Option<u8>,
+        input: Option<String>,
+    },
+    All,
+}
+
+enum Command {
+    /// The path is where to export the runs' stats.
+    Run(Selection, Format, Option<String>),
+    Verify(Selection, String),
+    Check(Selection),
+    Bench {
+        selection: Selection,
+        options: bench::Options,
+        baseline: Option<String>,
+        save: Option<String>,
+        threshold: f64,
+    },
+    Generate {
+        generator: &'static Generator,
+        seed: u64,
+        size: usize,
+    },
+    Animate {
+        animation: &'static Animation,
+        input: Option<String>,
+        frames: Frames,
+        gif: Option<String>,
+    },
+    List,
+}
+
+fn parse_selection<'a>(
+    mut args: impl Iterator<Item = &'a str>,
+    mut format: Format,
+) -> Result<Command, String> {
+    let mut positional = Vec::new();
+    let mut input = None;
+    let mut name = None;
+    let mut stats = None;
+    let mut all = false;
+    while let Some(arg) = args.next() {
+        match arg {
+            "--all" => all = true,
+            "--impl" => name = Some(args.next().ok_or("--impl needs a name")?),
+            "--input" => {
+                input = Some(args.next().ok_or("--input needs a path")?.to_owned());
+            }
+            "--format" => format = args.next().ok_or("--format needs a format")?.parse()?,
+            "--stats" if cfg!(feature = "tracing") => {
+                stats = Some(args.next().ok_or("--stats needs a path")?.to_owned());
+            }
+            "--stats" => return Err("--stats needs the tracing feature".to_owned()),
+            _ => positional.push(arg),
+        }
+    }
+    if all {
+        return if positional.is_empty() && input.is_none() && name.is_none() {
+            Ok(Command::Run(Selection::All, format, stats))
+        } else {
+            Err("--all doesn't take a day, part, implementation or input".to_owned())
+        };
+    }
+    let (day, part) = match positional[..] {
+        [day] => (day, None),
+        [day, part] => (day, Some(part)),
+        _ => return Err("expected a day and optionally a part".to_owned()),
+    };
+    let day = day
+        .parse()
+        .ok()
+        .and_then(runner::day)
+        .ok_or_else(|| format!("no such day: {}", day))?;
+    let day = match name {
+        Some(name) => runner::solution(day.year, day.day, name)
+            .ok_or_else(|| format!("no implementation of day {} called {}", day.day, name))?,
+        None => day,
+    };
+    let part = part
+        .map(|p| {
+            p.parse()
+                .ok()
+                .filter(|p| (1..=day.parts).contains(p))
+                .ok_or_else(|| format!("no such part for day {}: {}", day.day, p))
+        })
+        .transpose()?;
+    Ok(Command::Run(
+        Selection::One { day, part, input },
+        format,
+        stats,
+    ))
+}
+
+fn number<T: FromStr>(arg: Option<&str>, flag: &str) -> Result<T, String> {
+    arg.and_then(|n| n.parse().ok())
+        .ok_or_else(|| format!("{} needs a number", flag))
+}
+
+fn parse_verify<'a>(mut args: impl Iterator<Item = &'a str>) -> Result<Command, String> {
+    let mut rest = Vec::new();
+    let mut answers = DEFAULT_ANSWERS_PATH.to_owned();
+    while let Some(arg) = args.next() {
+        match arg {
+            "--answers" => answers = args.next().ok_or("--answers needs a path")?.to_owned(),
+            "--input" => rest.extend([arg, args.next().ok_or("--input needs a path")?]),
+            "--impl" => rest.extend([arg, args.next().ok_or("--impl needs a name")?]),
+            _ => rest.push(arg),
+        }
+    }
+    if rest.is_empty() {
+        return Ok(Command::Verify(Selection::All, answers));
+    }
+    match parse_selection(rest.into_iter(), Format::Text)? {
+        Command::Run(selection @ Selection::One { part: None, .. }, _, None) => {
+            Ok(Command::Verify(selection, answers))
+        }
+        Command::Run(_, _, Some(_)) => Err("verify doesn't take --stats".to_owned()),
+        _ => Err("verify takes a day but no part".to_owned()),
+    }
+}
+
+fn parse_check<'a>(args: impl Iterator<Item = &'a str>) -> Result<Command, String> {
+    match parse_selection(args, Format::Text)? {
+        Command::Run(_, _, Some(_)) => Err("check doesn't take --stats".to_owned()),
+        Command::Run(selection @ Selection::All, _, _) => Ok(Command::Check(selection)),
+        Command::Run(
+            selection @ Selection::One {
+                day, part: None, ..
+            },
+            _,
+            _,
+        ) if day.alternate().is_none() => Ok(Command::Check(selection)),
+        _ => Err("check takes a day but no part or implementation".to_owned()),
+    }
+}
+
+fn parse_bench<'a>(mut args: impl Iterator<Item = &'a str>) -> Result<Command, String> {
+    let mut rest = Vec::new();
+    let mut options = bench::Options::default();
+    let mut baseline = None;
+    let mut save = None;
+    let mut threshold = 10.0;
+    while let Some(arg) = args.next() {
+        match arg {
+            "--samples" => options.samples = number(args.next(), arg)?,
+            "--budget" => {
+                options.budget = Duration::try_from_secs_f64(number(args.next(), arg)?)
+                    .map_err(|_| "--budget needs a number of seconds")?;
+            }
+            "--threshold" => threshold = number(args.next(), arg)?,
+            "--baseline" => {
+                baseline = Some(args.next().ok_or("--baseline needs a path")?.to_owned());
+            }
+            "--save" => save = Some(args.next().ok_or("--save needs a path")?.to_owned()),
+            "--format" => return Err("bench doesn't take a format".to_owned()),
+            "--input" => rest.extend([arg, args.next().ok_or("--input needs a path")?]),
+            "--impl" => rest.extend([arg, args.next().ok_or("--impl needs a name")?]),
+            _ => rest.push(arg),
+        }
+    }
+    match parse_selection(rest.into_iter(), Format::Text)? {
+        Command::Run(_, _, Some(_)) => Err("bench doesn't take --stats".to_owned()),
+        Command::Run(selection, _, None) => Ok(Command::Bench {
+            selection,
+            options,
+            baseline,
+            save,
+            threshold: threshold / 100.0,
+        }),
+        _ => unreachable!("parse_selection only makes runs"),
+    }
+}
+
+fn parse_generate<'a>(mut args: impl Iterator<Item = &'a str>) -> Result<Command, String> {
+    let mut day = None;
+    let mut seed = 0;
+    let mut size = 10;
+    while let Some(arg) = args.next() {
+        match arg {
+            "--seed" => seed = number(args.next(), arg)?,
+            "--size" => size = number(args.next(), arg)?,
+            _ if day.is_none() => day = Some(arg),
+            _ => return Err(format!("unexpected argument: {}", arg)),
+        }
+    }
+    let day = day.ok_or("expected a day")?;
+    let generator = day
+        .parse()
+        .ok()
+        .and_then(inputgen::generator)
+        .ok_or_else(|| format!("no such day: {}", day))?;
+    Ok(Command::Generate {
+        generator,
+        seed,
+        size,
+    })
+}
+
+fn parse_animate<'a>(mut args: impl Iterator<Item = &'a str>) -> Result<Command, String> {
+    let mut day = None;
+    let mut input = None;
+    let mut every = 1;
+    let mut limit = None;
+    let mut gif = None;
+    while let Some(arg) = args.next() {
+        match arg {
+            "--input" => input = Some(args.next().ok_or("--input needs a path")?.to_owned()),
+            "--every" => every = number(args.next(), arg)?,
+            "--limit" => limit = Some(number(args.next(), arg)?),
+            "--gif" => gif = Some(args.next().ok_or("--gif needs a path")?.to_owned()),
+            _ if day.is_none() => day = Some(arg),
+            _ => return Err(format!("unexpected argument: {}", arg)),
+        }
+    }
+    let day = day.ok_or("expected a day")?;
+    let animation = day
+        .parse()
+        .ok()
+        .and_then(record::animation)
+        .ok_or_else(|| format!("no animation for day {}", day))?;
+    let frames = Frames::every(every);
+    let frames = match limit {
+        Some(limit) => frames.limit(limit),
+        None => frames,
+    };
+    Ok(Command::Animate {
+        animation,
+        input,
+        frames,
+        gif,
+    })
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let mut args = args.iter().map(String::as_str);
+    match args.next() {
+        Some("list") => Ok(Command::List),
+        Some("run") => parse_selection(args, Format::Text),
+        Some("report") => parse_selection(args, Format::JsonLines),
+        Some("verify") => parse_verify(args),
+        Some("check") => parse_check(args),
+        Some("bench") => parse_bench(args),
+        Some("generate") => parse_generate(args),
+        Some("animate") => parse_animate(args),
+        _ => Err("expected a command".to_owned()),
+    }
+}
+
+fn read_input(day: &Day, path: Option<&str>) -> Result<String, Box<dyn Error>> {
+    match path {
+        Some("-") => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            Ok(input)
+        }
+        Some(path) => {
+            Ok(fs::read_to_string(path).map_err(|e| format!("couldn't read {}: {}", path, e))?)
+        }
+        None => Provider::from_env(day.year).get(day.day),
+    }
+}
+
+fn run(
+    reporter: &mut Reporter<impl Write>,
+    mut exporter: Option<&mut Exporter<impl Write>>,
+    day: &Day,
+    part: Option<u8>,
+    input: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    let input = read_input(day, input)?;
+    for run in day.run(&input, part)? {
+        reporter.record(&run)?;
+        if let Some(exporter) = exporter.as_mut() {
+            exporter.export(&run)?;
+        }
+    }
+    if let Some(exporter) = exporter {
+        exporter.flush()?;
+    }
+    reporter.flush()
+}
+
+/// Print how each part compares to the registry, returning whether they all held up.
+fn verify(registry: &Registry, day: &Day, input: Option<&str>) -> Result<bool, Box<dyn Error>> {
+    let input = read_input(day, input)?;
+    let mut passed = true;
+    for verification in verify::verify(day, &input, registry) {
+        println!("{}", verification);
+        passed &= verification.passed();
+    }
+    Ok(passed)
+}
+
+/// Print whether each part's implementations agree, returning whether they all did.
+fn check(solutions: &[Day], input: Option<&str>) -> Result<bool, Box<dyn Error>> {
+    let input = read_input(&solutions[0], input)?;
+    let mut agreed = true;
+    for check in verify::cross_check(solutions, &input)? {
+        println!("{}", check);
+        agreed &= check.agreed();
+    }
+    Ok(agreed)
+}
+
+/// Print each timing, and how it compares to the baseline if there is one. Returns the timings
+/// and whether any of them regressed.
+fn bench(
+    day: &Day,
+    part: Option<u8>,
+    input: Option<&str>,
+    options: &bench::Options,
+    baseline: Option<(&Baseline, f64)>,
+) -> Result<(Vec<Measurement>, bool), Box<dyn Error>> {
+    let input = read_input(day, input)?;
+    let measurements = bench::bench(day, &input, part, options)?;
+    let mut regressed = false;
+    for m in measurements.iter() {
+        match baseline.and_then(|(b, threshold)| b.compare(m, threshold)) {
+            Some(change) => {
+                println!("{} [{}]", m, change);
+                regressed |= change.regressed;
+            }
+            None => println!("{}", m),
+        }
+    }
+    Ok((measurements, regressed))
+}
+
+fn animate(
+    animation: &Animation,
+    input: Option<&str>,
+    mut frames: Frames,
+    gif: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    let day = runner::day(animation.day).expect("animations are only for days that exist");
+    let input = read_input(day, input)?;
+    animation.run(&input, &mut frames)?;
+    match gif {
+        Some(path) => {
+            let out =
+                fs::File::create(path).map_err(|e| format!("couldn't write {}: {}", path, e))?;
+            frames.write_gif(io::BufWriter::new(out), GIF_SCALE, GIF_DELAY)?;
+        }
+        None => frames.write_text(io::stdout().lock())?,
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let command = match parse_args(&args) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    let result = match command {
+        Command::List => {
+            for solutions in runner::SOLUTIONS.iter() {
+                let day = &solutions[0];
+                let parts: Vec<String> = (1..=day.parts).map(|p| p.to_string()).collect();
+                print!("day {:2}: parts {}", day.day, parts.join(", "));
+                let names: Vec<String> = solutions
+                    .iter()
+                    .flat_map(|d| {
+                        let alternates = d.alternates().into_iter();
+                        [d.name.to_owned()]
+                            .into_iter()
+                            .chain(alternates.map(|(p, name)| format!("{} for part {}", name, p)))
+                    })
+                    .collect();
+                if names.len() > 1 {
+                    print!(" (implementations: {})", names.join(", "));
+                }
+                println!();
+            }
+            Ok(())
+        }
+        Command::Run(selection, format, stats) => stats
+            .map(|path| {
+                fs::File::create(&path)
+                    .map(|out| Exporter::new(io::BufWriter::new(out)))
+                    .map_err(|e| format!("couldn't write {}: {}", path, e).into())
+            })
+            .transpose()
+            .and_then(|mut exporter| {
+                let mut reporter = Reporter::new(format, io::stdout().lock());
+                match selection {
+                    Selection::One { day, part, input } => run(
+                        &mut reporter,
+                        exporter.as_mut(),
+                        day,
+                        part,
+                        input.as_deref(),
+                    ),
+                    Selection::All => runner::days()
+                        .try_for_each(|day| run(&mut reporter, exporter.as_mut(), day, None, None)),
+                }
+            }),
+        Command::Verify(selection, answers) => Registry::load(answers).and_then(|registry| {
+            let passed = match selection {
+                Selection::One { day, input, .. } => verify(&registry, day, input.as_deref())?,
+                Selection::All => runner::SOLUTIONS
+                    .iter()
+                    .flat_map(|s| s.iter())
+                    .try_fold(true, |passed, day| {
+                        Ok::<_, Box<dyn Error>>(verify(&registry, day, None)? && passed)
+                    })?,
+            };
+            if passed {
+                Ok(())
+            } else {
+                Err("some answers don't match".into())
+            }
+        }),
+        Command::Check(selection) => match selection {
+            Selection::One { day, input, .. } => {
+                check(runner::solutions(day.year, day.day), input.as_deref())
+            }
+            Selection::All => runner::SOLUTIONS
+                .iter()
+                .try_fold(true, |agreed, solutions| {
+                    Ok(check(solutions, None)? && agreed)
+                }),
+        }
+        .and_then(|agreed| {
+            if agreed {
+                Ok(())
+            } else {
+                Err("some implementations disagree".into())
+            }
+        }),
+        Command::Bench {
+            selection,
+            options,
+            baseline,
+            save,
+            threshold,
+        } => baseline
+            .map(Baseline::load)
+            .transpose()
+            .and_then(|baseline| {
+                let baseline = baseline.as_ref().map(|b| (b, threshold));
+                let (measurements, regressed) = match selection {
+                    Selection::One { day, part, input } => {
+                        bench(day, part, input.as_deref(), &options, baseline)?
+                    }
+                    Selection::All => runner::days().try_fold(
+                        (Vec::new(), false),
+                        |(mut all, regressed), day| {
+                            let (ms, r) = bench(day, None, None, &options, baseline)?;
+                            all.extend(ms);
+                            Ok::<_, Box<dyn Error>>((all, regressed || r))
+                        },
+                    )?,
+                };
+                if let Some(path) = save {
+                    let out = fs::File::create(&path)
+                        .map_err(|e| format!("couldn't write {}: {}", path, e))?;
+                    Baseline::write(&measurements, out)?;
+                }
+                if regressed {
+                    Err("some timings regressed".into())
+                } else {
+                    Ok(())
+                }
+            }),
+        Command::Generate {
+            generator,
+            seed,
+            size,
+        } => {
+            print!("{}", generator.generate(seed, size));
+            Ok(())
+        }
+        Command::Animate {
+            animation,
+            input,
+            frames,
+            gif,
+        } => animate(animation, input.as_deref(), frames, gif.as_deref()),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::{
+    inputgen,
+    runner::{self, Answer, Generated},
+    solution::YEAR,
+};
+
+fn to_py(py: Python<'_>, answer: Answer) -> PyObject {
+    match answer {
+        Answer::Usize(n) => n.into_py(py),
+        Answer::Isize(n) => n.into_py(py),
+        Answer::U64(n) => n.into_py(py),
+        Answer::I64(n) => n.into_py(py),
+        Answer::String(s) => s.into_py(py),
+    }
+}
+
+/// One of a day's solutions: `Day.generator(input)` parses, and `Day.part_1(input)` and
+/// `Day.part_2(input)` parse and solve.
+#[pyclass(name = "Day", frozen)]
+struct PyDay(&'static runner::Day);
+
+/// A day's input after its generator has run, ready for `Parsed.solve(part)`.
+#[pyclass(name = "Parsed", unsendable)]
+struct PyParsed(Box<dyn Generated>);
+
+#[pymethods]
+impl PyDay {
+    #[getter]
+    fn day(&self) -> u8 {
+        self.0.day
+    }
+
+    /// `"default"`, or the name of an alternate solution.
+    #[getter]
+    fn name(&self) -> &'static str {
+        self.0.name
+    }
+
+    #[getter]
+    fn parts(&self) -> u8 {
+        self.0.parts
+    }
+
+    fn generator(&self, input: &str) -> PyResult<PyParsed> {
+        self.0
+            .generate(input)
+            .map(PyParsed)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    fn solve(&self, py: Python<'_>, part: u8, input: &str) -> PyResult<PyObject> {
+        self.generator(input)?.solve(py, part)
+    }
+
+    fn part_1(&self, py: Python<'_>, input: &str) -> PyResult<PyObject> {
+        self.solve(py, 1, input)
+    }
+
+    fn part_2(&self, py: Python<'_>, input: &str) -> PyResult<PyObject> {
+        self.solve(py, 2, input)
+    }
+
+    fn __repr__(&self) -> String {
+        match self.0.alternate() {
+            Some(name) => format!("<Day {} ({})>", self.0.day, name),
+            None => format!("<Day {}>", self.0.day),
+        }
+    }
+}
+
+#[pymethods]
+impl PyParsed {
+    fn solve(&self, py: Python<'_>, part: u8) -> PyResult<PyObject> {
+        let answer = self
+            .0
+            .solve(part)
+            .ok_or_else(|| PyValueError::new_err(format!("no such part: {}", part)))?;
+        Ok(to_py(py, answer))
+    }
+}
+
+/// The day's default solution, or the one called `name`.
+#[pyfunction]
+#[pyo3(signature = (n, name = None))]
+fn day(n: u8, name: Option<&str>) -> PyResult<PyDay> {
+    let day = runner::day(n).ok_or_else(|| PyValueError::new_err(format!("no such day: {}", n)))?;
+    match name {
+        Some(name) => runner::solution(day.year, n, name)
+            .map(PyDay)
+            .ok_or_else(|| {
+                PyValueError::new_err(format!("no implementation of day {} called {}", n, name))
+            }),
+        None => Ok(PyDay(day)),
+    }
+}
+
+/// Every solution registered for the day, default first.
+#[pyfunction]
+fn solutions(n: u8) -> Vec<PyDay> {
+    runner::solutions(YEAR, n).iter().map(PyDay).collect()
+}
+
+/// A random input for the day, as from `aoc_2022 generate`.
+#[pyfunction]
+#[pyo3(signature = (day, seed = 0, size = 10))]
+fn generate_input(day: u8, seed: u64, size: usize) -> PyResult<String> {
+    inputgen::generator(day)
+        .map(|g| g.generate(seed, size))
+        .ok_or_else(|| PyValueError::new_err(format!("no such day: {}", day)))
+}
+
+/// The Rust solutions, for the Python package. Build into the current virtualenv with
+/// `maturin develop --release` from `rs/`.
+#[pymodule]
+fn aoc_2022_rs(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
+    m.add_class::<PyDay>()?;
+    m.add_class::<PyParsed>()?;
+    m.add_function(wrap_pyfunction!(day, m)?)?;
+    m.add_function(wrap_pyfunction!(solutions, m)?)?;
+    m.add_function(wrap_pyfunction!(generate_input, m)?)?;
+    Ok(())
+}
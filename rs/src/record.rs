@@ -0,0 +1,177 @@
+use std::io::{self, Write};
+
+use crate::{error::ParseError, render::Canvas};
+
+/// Where a simulation sends a picture of itself after each step.
+pub trait Recorder {
+    /// `draw` only gets called if the frame is wanted, so a simulation can hand over a frame every
+    /// step without slowing down when nothing is recording.
+    fn record(&mut self, draw: impl FnOnce() -> Canvas);
+}
+
+/// Throws every frame away. This is what the solutions themselves run with.
+pub struct Discard;
+
+impl Recorder for Discard {
+    fn record(&mut self, _draw: impl FnOnce() -> Canvas) {}
+}
+
+/// Keeps every `every`th frame, numbered by the step it was taken at, up to an optional limit.
+#[derive(Clone, Debug)]
+pub struct Frames {
+    pub frames: Vec<(usize, Canvas)>,
+    every: usize,
+    limit: Option<usize>,
+    step: usize,
+}
+
+impl Default for Frames {
+    fn default() -> Self {
+        Self::every(1)
+    }
+}
+
+impl Recorder for Frames {
+    fn record(&mut self, draw: impl FnOnce() -> Canvas) {
+        if self.step.is_multiple_of(self.every) && self.limit.is_none_or(|l| self.frames.len() < l)
+        {
+            self.frames.push((self.step, draw()));
+        }
+        self.step += 1;
+    }
+}
+
+impl Frames {
+    pub fn every(n: usize) -> Self {
+        Self {
+            frames: Vec::new(),
+            every: n.max(1),
+            limit: None,
+            step: 0,
+        }
+    }
+
+    /// Stop keeping frames once there are `n` of them.
+    pub fn limit(self, n: usize) -> Self {
+        Self {
+            limit: Some(n),
+            ..self
+        }
+    }
+
+    /// Write each frame as text, under a `-- frame N --` header.
+    pub fn write_text(&self, mut out: impl Write) -> io::Result<()> {
+        for (step, canvas) in self.frames.iter() {
+            writeln!(out, "-- frame {} --\n{}\n", step, canvas)?;
+        }
+        Ok(())
+    }
+
+    /// Write the frames as a looping GIF, `delay` hundredths of a second apart. Frames smaller
+    /// than the largest one are padded out with black to the right and below.
+    pub fn write_gif(
+        &self,
+        out: impl Write,
+        scale: usize,
+        delay: u16,
+    ) -> Result<(), gif::EncodingError> {
+        let size = |f: fn(&Canvas) -> usize| {
+            let n = self.frames.iter().map(|(_, c)| f(c)).max().unwrap_or(0) * scale;
+            u16::try_from(n.max(1)).map_err(|_| {
+                io::Error::new(io::ErrorKind::InvalidInput, "frames too big for a GIF")
+            })
+        };
+        let (w, h) = (size(Canvas::w)?, size(Canvas::h)?);
+        let mut encoder = gif::Encoder::new(out, w, h, &[])?;
+        encoder.set_repeat(gif::Repeat::Infinite)?;
+        for (_, canvas) in self.frames.iter() {
+            let pixels = canvas.pixels_within(scale, (w as usize, h as usize));
+            let mut frame = gif::Frame::from_rgb_speed(w, h, &pixels, 10);
+            frame.delay = delay;
+            encoder.write_frame(&frame)?;
+        }
+        Ok(())
+    }
+}
+
+/// A day whose simulation can be watched, and which part of it gets shown.
+pub struct Animation {
+    pub day: u8,
+    pub part: u8,
+    run: fn(&str, &mut Frames) -> Result<(), ParseError>,
+}
+
+impl Animation {
+    /// Parse the input and run the simulation, recording it into `frames`.
+    pub fn run(&self, input: &str, frames: &mut Frames) -> Result<(), ParseError> {
+        (self.run)(input.trim_end_matches('\n'), frames)
+    }
+}
+
+macro_rules! animation {
+    ($day:literal, $part:literal, $module:ident) => {
+        Animation {
+            day: $day,
+            part: $part,
+            run: |input, frames| {
+                crate::$module::animate(&crate::$module::get_input(input)?, frames);
+                Ok(())
+            },
+        }
+    };
+}
+
+pub static ANIMATIONS: [Animation; 5] = [
+    animation!(9, 2, day_09),
+    animation!(14, 2, day_14),
+    animation!(17, 1, day_17),
+    animation!(23, 2, day_23),
+    animation!(24, 1, day_24),
+];
+
+pub fn animation(day: u8) -> Option<&'static Animation> {
+    ANIMATIONS.iter().find(|a| a.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frames() -> Frames {
+        let mut frames = Frames::every(2).limit(2);
+        for c in ['a', 'b', 'c', 'd', 'e'] {
+            frames.record(|| Canvas::from_points([(0, 0), (c as isize - 'a' as isize, 0)], c, '.'));
+        }
+        frames
+    }
+
+    #[test]
+    fn test_text_frames() {
+        let mut out = Vec::new();
+        frames().write_text(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "-- frame 0 --\na\n\n-- frame 2 --\nc.c\n\n"
+        );
+        let mut drawn = 0;
+        Discard.record(|| {
+            drawn += 1;
+            Canvas::from_points([], '#', '.')
+        });
+        assert_eq!(drawn, 0);
+    }
+
+    #[test]
+    fn test_gif() {
+        let mut out = Vec::new();
+        frames().write_gif(&mut out, 2, 5).unwrap();
+        assert_eq!(&out[..6], b"GIF89a");
+        assert_eq!(&out[6..10], &[6, 0, 2, 0]);
+        let mut frames = Frames::default();
+        animation(17)
+            .unwrap()
+            .run(">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>\n", &mut frames)
+            .unwrap();
+        assert_eq!(frames.frames.len(), 2022);
+    }
+}
@@ -0,0 +1,245 @@
+use std::{
+    fmt,
+    io::{self, Write},
+    iter,
+};
+
+use crate::grid::Grid;
+
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+pub const RED: Rgb = [255, 0, 0];
+
+/// How something looks when drawn as a single cell: a character for text, a color for images.
+pub trait Glyph {
+    fn glyph(&self) -> char;
+
+    /// Defaults to black for blank-looking glyphs and white for everything else.
+    fn color(&self) -> Rgb {
+        match self.glyph() {
+            ' ' | '.' => BLACK,
+            _ => WHITE,
+        }
+    }
+}
+
+impl Glyph for char {
+    fn glyph(&self) -> char {
+        *self
+    }
+}
+
+impl Glyph for u8 {
+    fn glyph(&self) -> char {
+        *self as char
+    }
+}
+
+impl Glyph for bool {
+    fn glyph(&self) -> char {
+        if *self {
+            '█'
+        } else {
+            ' '
+        }
+    }
+}
+
+impl Glyph for (char, Rgb) {
+    fn glyph(&self) -> char {
+        self.0
+    }
+
+    fn color(&self) -> Rgb {
+        self.1
+    }
+}
+
+/// A rectangle of glyphs, ready to be written out as text or as an image. Cells are addressed
+/// in the coordinates of whatever the canvas was drawn from, so overlays can use the same points.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Canvas {
+    /// Coordinates of the top-left cell.
+    origin: (isize, isize),
+    cells: Grid<(char, Rgb)>,
+}
+
+impl Canvas {
+    pub fn from_grid<T: Glyph>(grid: &Grid<T>) -> Self {
+        Self {
+            origin: (0, 0),
+            cells: Grid {
+                items: grid.items.iter().map(|t| (t.glyph(), t.color())).collect(),
+                w: grid.w,
+            },
+        }
+    }
+
+    /// Draw the bounding box of `points`, with `on` at each point and `off` everywhere else.
+    pub fn from_points(
+        points: impl IntoIterator<Item = (isize, isize)>,
+        on: impl Glyph,
+        off: impl Glyph,
+    ) -> Self {
+        let points: Vec<(isize, isize)> = points.into_iter().collect();
+        let (min_x, max_x) = bounds(points.iter().map(|p| p.0));
+        let (min_y, max_y) = bounds(points.iter().map(|p| p.1));
+        let w = (max_x - min_x) as usize;
+        let h = (max_y - min_y) as usize;
+        let mut canvas = Self {
+            origin: (min_x, min_y),
+            cells: Grid {
+                items: vec![(off.glyph(), off.color()); w * h],
+                w,
+            },
+        };
+        canvas.highlight(points, on);
+        canvas
+    }
+
+    pub fn w(&self) -> usize {
+        self.cells.w
+    }
+
+    pub fn h(&self) -> usize {
+        self.cells.h()
+    }
+
+    fn index(&self, (x, y): (isize, isize)) -> Option<usize> {
+        let x = usize::try_from(x - self.origin.0).ok()?;
+        let y = usize::try_from(y - self.origin.1).ok()?;
+        (x < self.cells.w && y < self.cells.h()).then(|| self.cells.xy_to_i((x, y)))
+    }
+
+    /// Draw `glyph` over each of `points`, skipping any that fall outside the canvas.
+    pub fn highlight(
+        &mut self,
+        points: impl IntoIterator<Item = (isize, isize)>,
+        glyph: impl Glyph,
+    ) -> &mut Self {
+        for p in points {
+            if let Some(i) = self.index(p) {
+                self.cells[i] = (glyph.glyph(), glyph.color());
+            }
+        }
+        self
+    }
+
+    /// Draw a route through orthogonally adjacent points as arrows, each pointing at the next
+    /// point, with a `*` on the last one.
+    pub fn path(
+        &mut self,
+        points: impl IntoIterator<Item = (isize, isize)>,
+        color: Rgb,
+    ) -> &mut Self {
+        let points: Vec<(isize, isize)> = points.into_iter().collect();
+        for (i, &p) in points.iter().enumerate() {
+            let glyph = match points.get(i + 1).map(|q| (q.0 - p.0, q.1 - p.1)) {
+                None => '*',
+                Some((0, dy)) if dy < 0 => '↑',
+                Some((0, dy)) if dy > 0 => '↓',
+                Some((dx, _)) if dx < 0 => '←',
+                Some((dx, _)) if dx > 0 => '→',
+                Some(_) => '•',
+            };
+            self.highlight([p], (glyph, color));
+        }
+        self
+    }
+
+    /// Raw RGB bytes, row by row, with each cell blown up to a `scale` × `scale` square.
+    fn pixels(&self, scale: usize) -> Vec<u8> {
+        self.pixels_within(scale, (self.w() * scale, self.h() * scale))
+    }
+
+    /// Same as [`Canvas::pixels`], but cropped or padded with black to `w` × `h` pixels.
+    pub(crate) fn pixels_within(&self, scale: usize, (w, h): (usize, usize)) -> Vec<u8> {
+        let mut pixels = Vec::with_capacity(w * h * 3);
+        let rows = self.cells.rows().flat_map(|row| iter::repeat_n(row, scale));
+        for row in rows.take(h) {
+            let mut line: Vec<u8> = row.iter().flat_map(|&(_, rgb)| rgb.repeat(scale)).collect();
+            line.resize(w * 3, 0);
+            pixels.extend(line);
+        }
+        pixels.resize(w * h * 3, 0);
+        pixels
+    }
+
+    /// Write a binary PPM (P6) image.
+    pub fn write_ppm(&self, mut out: impl Write, scale: usize) -> io::Result<()> {
+        let (w, h) = (self.cells.w * scale, self.cells.h() * scale);
+        write!(out, "P6\n{} {}\n255\n", w, h)?;
+        out.write_all(&self.pixels(scale))
+    }
+
+    pub fn write_png(&self, out: impl Write, scale: usize) -> Result<(), png::EncodingError> {
+        let (w, h) = (self.cells.w * scale, self.cells.h() * scale);
+        let mut encoder = png::Encoder::new(out, w as u32, h as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels(scale))
+    }
+}
+
+/// Lowest value and one past the highest, or `(0, 0)` if there aren't any.
+fn bounds(ns: impl Iterator<Item = isize>) -> (isize, isize) {
+    ns.fold(None, |acc, n| match acc {
+        None => Some((n, n + 1)),
+        Some((lo, hi)) => Some((n.min(lo), hi.max(n + 1))),
+    })
+    .unwrap_or((0, 0))
+}
+
+/// One line of text per row, with no newline after the last.
+impl fmt::Display for Canvas {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.cells.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for &(c, _) in row {
+                write!(f, "{}", c)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text() {
+        let grid = Grid::<u8>::try_from("#..\n.#.").unwrap();
+        assert_eq!(Canvas::from_grid(&grid).to_string(), "#..\n.#.");
+        let points = [(-1, 5), (1, 6), (0, 7)];
+        let mut canvas = Canvas::from_points(points, '#', '.');
+        assert_eq!(canvas.to_string(), "#..\n..#\n.#.");
+        canvas.highlight([(0, 5), (9, 9)], 'o');
+        canvas.path([(-1, 6), (-1, 7), (0, 7)], RED);
+        assert_eq!(canvas.to_string(), "#o.\n↓.#\n→*.");
+        assert_eq!(Canvas::from_points([], '#', '.').to_string(), "");
+    }
+
+    #[test]
+    fn test_images() {
+        let mut canvas = Canvas::from_grid(&Grid {
+            items: vec![true, false],
+            w: 2,
+        });
+        canvas.highlight([(1, 0)], ('x', RED));
+        let mut ppm = Vec::new();
+        canvas.write_ppm(&mut ppm, 2).unwrap();
+        let header = b"P6\n4 2\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        let row = [WHITE, WHITE, RED, RED].concat();
+        assert_eq!(&ppm[header.len()..], [row.clone(), row].concat());
+        let mut png = Vec::new();
+        canvas.write_png(&mut png, 1).unwrap();
+        assert_eq!(&png[1..4], b"PNG");
+    }
+}
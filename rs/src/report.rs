@@ -0,0 +1,160 @@
+use std::{error::Error, io::Write, str::FromStr};
+
+use serde::Serialize;
+
+use crate::runner::{Answer, Run};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Same layout as cargo-aoc prints.
+    Text,
+    /// One JSON object per line.
+    #[default]
+    JsonLines,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" | "jsonl" => Ok(Format::JsonLines),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("unknown format: {}", s)),
+        }
+    }
+}
+
+/// Flattened form of a [`Run`], with times in nanoseconds.
+#[derive(Clone, Debug, Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Answer,
+    pub answer_type: &'static str,
+    pub generator_ns: u64,
+    pub solve_ns: u64,
+}
+
+impl From<&Run> for Record {
+    fn from(run: &Run) -> Self {
+        Self {
+            day: run.day,
+            part: run.part,
+            answer: run.answer.clone(),
+            answer_type: run.answer.type_name(),
+            generator_ns: run.generator_time.as_nanos() as u64,
+            solve_ns: run.solve_time.as_nanos() as u64,
+        }
+    }
+}
+
+/// Writes one record per run. CSV output gets a header row before the first record.
+pub struct Reporter<W: Write> {
+    format: Format,
+    out: Output<W>,
+}
+
+enum Output<W: Write> {
+    Plain(W),
+    Csv(Box<csv::Writer<W>>),
+}
+
+impl<W: Write> Reporter<W> {
+    pub fn new(format: Format, out: W) -> Self {
+        let out = match format {
+            Format::Csv => Output::Csv(Box::new(csv::Writer::from_writer(out))),
+            _ => Output::Plain(out),
+        };
+        Self { format, out }
+    }
+
+    pub fn record(&mut self, run: &Run) -> Result<(), Box<dyn Error>> {
+        match (&mut self.out, self.format) {
+            (Output::Plain(out), Format::Text) => writeln!(
+                out,
+                "Day {} - Part {}: {}\n\tgenerator: {:?},\n\trunner: {:?}\n",
+                run.day, run.part, run.answer, run.generator_time, run.solve_time
+            )?,
+            (Output::Plain(out), _) => {
+                serde_json::to_writer(&mut *out, &Record::from(run))?;
+                writeln!(out)?;
+            }
+            (Output::Csv(out), _) => out.serialize(Record::from(run))?,
+        }
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<(), Box<dyn Error>> {
+        match &mut self.out {
+            Output::Plain(out) => out.flush()?,
+            Output::Csv(out) => out.flush()?,
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::stats::Counters;
+
+    fn runs() -> Vec<Run> {
+        vec![
+            Run {
+                day: 5,
+                part: 1,
+                answer: Answer::String("CMZ".to_owned()),
+                generator_time: Duration::from_nanos(1500),
+                solve_time: Duration::from_nanos(20),
+                stats: Counters::default(),
+            },
+            Run {
+                day: 21,
+                part: 2,
+                answer: Answer::I64(-301),
+                generator_time: Duration::from_nanos(7),
+                solve_time: Duration::from_nanos(3),
+                stats: Counters::default(),
+            },
+        ]
+    }
+
+    fn report(format: Format) -> String {
+        let mut out = Vec::new();
+        let mut reporter = Reporter::new(format, &mut out);
+        for run in runs().iter() {
+            reporter.record(run).unwrap();
+        }
+        reporter.flush().unwrap();
+        drop(reporter);
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_json_lines() {
+        assert_eq!(
+            report(Format::JsonLines),
+            concat!(
+                r#"{"day":5,"part":1,"answer":"CMZ","answer_type":"String","generator_ns":1500,"solve_ns":20}"#,
+                "\n",
+                r#"{"day":21,"part":2,"answer":-301,"answer_type":"i64","generator_ns":7,"solve_ns":3}"#,
+                "\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_csv() {
+        assert_eq!(
+            report(Format::Csv),
+            "day,part,answer,answer_type,generator_ns,solve_ns\n\
+            5,1,CMZ,String,1500,20\n\
+            21,2,-301,i64,7,3\n"
+        );
+    }
+}
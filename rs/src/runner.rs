@@ -0,0 +1,317 @@
+use std::{
+    convert::Infallible,
+    fmt,
+    marker::PhantomData,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+use crate::{
+    day_01, day_02, day_03, day_04, day_05, day_06, day_07, day_08, day_09, day_10, day_11, day_12,
+    day_13, day_14, day_15, day_16, day_17, day_18, day_19, day_20, day_21, day_22, day_23, day_24,
+    day_25,
+    error::ParseError,
+    solution::{self, Solution},
+    stats::{self, Counters},
+};
+
+/// Answers come back as whichever of these the part function happens to return. Parts whose
+/// answers might not fit in 32 bits return `u64` or `i64`, so they come out the same on wasm32.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Usize(usize),
+    Isize(isize),
+    U64(u64),
+    I64(i64),
+    String(String),
+}
+
+impl Answer {
+    /// Name of the Rust type the part function returned.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Answer::Usize(_) => "usize",
+            Answer::Isize(_) => "isize",
+            Answer::U64(_) => "u64",
+            Answer::I64(_) => "i64",
+            Answer::String(_) => "String",
+        }
+    }
+
+    /// Inverse of [`Answer::type_name`] paired with [`Display`](fmt::Display), for reading
+    /// answers back in from a report.
+    pub fn from_typed(type_name: &str, answer: &str) -> Result<Self, String> {
+        let invalid = |_| format!("invalid {} answer: {:?}", type_name, answer);
+        match type_name {
+            "usize" => answer.parse().map(Answer::Usize).map_err(invalid),
+            "isize" => answer.parse().map(Answer::Isize).map_err(invalid),
+            "u64" => answer.parse().map(Answer::U64).map_err(invalid),
+            "i64" => answer.parse().map(Answer::I64).map_err(invalid),
+            "String" => Ok(Answer::String(answer.to_owned())),
+            _ => Err(format!("unknown answer type: {}", type_name)),
+        }
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Usize(n)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(n: isize) -> Self {
+        Answer::Isize(n)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        Answer::U64(n)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Answer::I64(n)
+    }
+}
+
+impl From<Infallible> for Answer {
+    fn from(n: Infallible) -> Self {
+        match n {}
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::String(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::String(s.to_owned())
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Usize(n) => write!(f, "{}", n),
+            Answer::Isize(n) => write!(f, "{}", n),
+            Answer::U64(n) => write!(f, "{}", n),
+            Answer::I64(n) => write!(f, "{}", n),
+            Answer::String(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// Output of a day's generator, with its type erased so that every day looks the same.
+pub trait Generated {
+    fn solve(&self, part: u8) -> Option<Answer>;
+
+    /// Solve `part` with the alternate called `name` instead, if there's one by that name.
+    fn solve_alternate(&self, part: u8, name: &str) -> Option<Answer>;
+}
+
+/// A solution's parsed input, tagged with the solution that's going to solve it.
+struct Parsed<S: Solution>(S::Input, PhantomData<S>);
+
+impl<S: Solution> Generated for Parsed<S> {
+    fn solve(&self, part: u8) -> Option<Answer> {
+        match part {
+            1 => Some(S::part1(&self.0)),
+            2 if S::PARTS >= 2 => Some(S::part2(&self.0)),
+            _ => None,
+        }
+    }
+
+    fn solve_alternate(&self, part: u8, name: &str) -> Option<Answer> {
+        S::ALTERNATES
+            .iter()
+            .find(|a| (a.part, a.name) == (part, name))
+            .map(|a| (a.solve)(&self.0))
+    }
+}
+
+/// A registered [`Solution`], with its types erased so that every day looks the same.
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub name: &'static str,
+    pub parts: u8,
+    generator: fn(&str) -> Result<Box<dyn Generated>, ParseError>,
+    alternates: fn() -> Vec<(u8, &'static str)>,
+}
+
+/// The answer to one part, along with how long it took to get there.
+#[derive(Clone, Debug)]
+pub struct Run {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Answer,
+    pub generator_time: Duration,
+    pub solve_time: Duration,
+    /// Counters from inside the solver, with the `tracing` feature.
+    pub stats: Counters,
+}
+
+impl Day {
+    pub const fn of<S: Solution + 'static>() -> Self {
+        Self {
+            year: S::YEAR,
+            day: S::DAY,
+            name: S::NAME,
+            parts: S::PARTS,
+            generator: generate::<S>,
+            alternates: alternates::<S>,
+        }
+    }
+
+    /// The part and name of each of the solution's single-part [alternates].
+    ///
+    /// [alternates]: solution::Alternate
+    pub fn alternates(&self) -> Vec<(u8, &'static str)> {
+        (self.alternates)()
+    }
+
+    /// Every answer this solution has for `part`: its own first, under `None`, then each
+    /// alternate's under its name. The generator runs afresh for each, as in [`Day::run`].
+    pub fn solve_all(
+        &self,
+        input: &str,
+        part: u8,
+    ) -> Result<Vec<(Option<&'static str>, Answer)>, ParseError> {
+        let mut answers = Vec::new();
+        if let Some(answer) = self.generate(input)?.solve(part) {
+            answers.push((None, answer));
+        }
+        for (_, name) in self.alternates().into_iter().filter(|&(p, _)| p == part) {
+            if let Some(answer) = self.generate(input)?.solve_alternate(part, name) {
+                answers.push((Some(name), answer));
+            }
+        }
+        Ok(answers)
+    }
+
+    /// The name of this solution if it isn't the day's default.
+    pub fn alternate(&self) -> Option<&'static str> {
+        Some(self.name).filter(|&name| name != solution::DEFAULT_NAME)
+    }
+
+    /// Parse `input` the solution's way; the days' `FromStr` impls drop trailing newlines first,
+    /// as cargo-aoc does.
+    pub fn generate(&self, input: &str) -> Result<Box<dyn Generated>, ParseError> {
+        (self.generator)(input)
+    }
+
+    /// Solve either the given part or all of them. As with cargo-aoc, each part gets its own run
+    /// of the generator, since some parts (e.g. day 5's) leave their mark on the shared input.
+    pub fn run(&self, input: &str, part: Option<u8>) -> Result<Vec<Run>, ParseError> {
+        let mut runs = Vec::new();
+        for part in part.map_or(1..=self.parts, |p| p..=p) {
+            #[cfg(feature = "tracing")]
+            let _span =
+                tracing::info_span!("run", day = self.day, part, name = self.name).entered();
+            let start = Instant::now();
+            let generated = self.generate(input)?;
+            let generator_time = start.elapsed();
+            // only what the part itself records goes in its stats
+            stats::take();
+            let start = Instant::now();
+            if let Some(answer) = generated.solve(part) {
+                let solve_time = start.elapsed();
+                runs.push(Run {
+                    day: self.day,
+                    part,
+                    answer,
+                    generator_time,
+                    solve_time,
+                    stats: stats::take(),
+                });
+            }
+        }
+        Ok(runs)
+    }
+}
+
+fn generate<S: Solution + 'static>(input: &str) -> Result<Box<dyn Generated>, ParseError> {
+    Ok(Box::new(Parsed::<S>(S::parse(input)?, PhantomData)))
+}
+
+fn alternates<S: Solution>() -> Vec<(u8, &'static str)> {
+    S::ALTERNATES.iter().map(|a| (a.part, a.name)).collect()
+}
+
+/// Every day's solutions, in order, as each `day_XX` module registers them: the default first,
+/// then any alternates.
+pub static SOLUTIONS: [&[Day]; 25] = [
+    &day_01::SOLUTIONS,
+    &day_02::SOLUTIONS,
+    &day_03::SOLUTIONS,
+    &day_04::SOLUTIONS,
+    &day_05::SOLUTIONS,
+    &day_06::SOLUTIONS,
+    &day_07::SOLUTIONS,
+    &day_08::SOLUTIONS,
+    &day_09::SOLUTIONS,
+    &day_10::SOLUTIONS,
+    &day_11::SOLUTIONS,
+    &day_12::SOLUTIONS,
+    &day_13::SOLUTIONS,
+    &day_14::SOLUTIONS,
+    &day_15::SOLUTIONS,
+    &day_16::SOLUTIONS,
+    &day_17::SOLUTIONS,
+    &day_18::SOLUTIONS,
+    &day_19::SOLUTIONS,
+    &day_20::SOLUTIONS,
+    &day_21::SOLUTIONS,
+    &day_22::SOLUTIONS,
+    &day_23::SOLUTIONS,
+    &day_24::SOLUTIONS,
+    &day_25::SOLUTIONS,
+];
+
+/// All the solutions registered for a day, default first; empty if there aren't any.
+pub fn solutions(year: u16, day: u8) -> &'static [Day] {
+    SOLUTIONS
+        .iter()
+        .find(|s| s.first().is_some_and(|d| (d.year, d.day) == (year, day)))
+        .copied()
+        .unwrap_or_default()
+}
+
+pub fn solution(year: u16, day: u8, name: &str) -> Option<&'static Day> {
+    solutions(year, day).iter().find(|d| d.name == name)
+}
+
+/// The default solution for a day this year.
+pub fn day(n: u8) -> Option<&'static Day> {
+    solutions(solution::YEAR, n).first()
+}
+
+/// Every day's default solution, in order.
+pub fn days() -> impl Iterator<Item = &'static Day> {
+    SOLUTIONS.iter().filter_map(|s| s.first())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run() {
+        let input = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+        let runs = day(1).unwrap().run(input, None).unwrap();
+        let answers: Vec<_> = runs.iter().map(|r| (r.part, r.answer.clone())).collect();
+        assert_eq!(
+            answers,
+            vec![(1, Answer::Usize(24000)), (2, Answer::Usize(45000))]
+        );
+        assert!(day(25).unwrap().run("1=\n", Some(2)).unwrap().is_empty());
+    }
+}
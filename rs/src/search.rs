@@ -0,0 +1,199 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+};
+
+/// A state space to search through, described by the moves out of each state.
+pub trait Space {
+    type State: Clone + Eq + Hash;
+
+    /// The states one move away from `state`, each with the cost of that move.
+    fn successors(&self, state: &Self::State) -> Vec<(Self::State, usize)>;
+}
+
+/// The cheapest route found to a target, from whichever source it started at.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path<S> {
+    pub cost: usize,
+    /// Every state along the way, starting with the source and ending with the target.
+    pub states: Vec<S>,
+}
+
+/// Visited states, each pointing back at the one it was reached from.
+struct Trail<S> {
+    nodes: Vec<(S, Option<usize>)>,
+    seen: HashMap<S, usize>,
+}
+
+impl<S: Clone + Eq + Hash> Trail<S> {
+    fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            seen: HashMap::new(),
+        }
+    }
+
+    fn push(&mut self, state: S, parent: Option<usize>) -> usize {
+        self.nodes.push((state, parent));
+        self.nodes.len() - 1
+    }
+
+    fn path(&self, mut node: usize, cost: usize) -> Path<S> {
+        let mut states = vec![self.nodes[node].0.clone()];
+        while let Some(parent) = self.nodes[node].1 {
+            states.push(self.nodes[parent].0.clone());
+            node = parent;
+        }
+        states.reverse();
+        Path { cost, states }
+    }
+}
+
+/// Breadth-first search, which treats every move as costing 1 whatever the space says.
+pub fn bfs<P: Space>(
+    space: &P,
+    sources: impl IntoIterator<Item = P::State>,
+    is_target: impl Fn(&P::State) -> bool,
+) -> Option<Path<P::State>> {
+    let mut trail = Trail::new();
+    let mut queue = VecDeque::new();
+    for source in sources {
+        if trail.seen.insert(source.clone(), 0).is_none() {
+            let node = trail.push(source, None);
+            queue.push_back((node, 0));
+        }
+    }
+    while let Some((node, steps)) = queue.pop_front() {
+        let state = trail.nodes[node].0.clone();
+        if is_target(&state) {
+            return Some(trail.path(node, steps));
+        }
+        for (next, _) in space.successors(&state) {
+            if trail.seen.insert(next.clone(), steps + 1).is_none() {
+                let next = trail.push(next, Some(node));
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+    None
+}
+
+/// Dijkstra's algorithm, i.e. A* with nothing to go on.
+pub fn dijkstra<P: Space>(
+    space: &P,
+    sources: impl IntoIterator<Item = P::State>,
+    is_target: impl Fn(&P::State) -> bool,
+) -> Option<Path<P::State>> {
+    astar(space, sources, is_target, |_| 0)
+}
+
+/// A* search. `heuristic` must never overestimate the remaining cost to the nearest target, or
+/// the path found may not be the cheapest.
+pub fn astar<P: Space>(
+    space: &P,
+    sources: impl IntoIterator<Item = P::State>,
+    is_target: impl Fn(&P::State) -> bool,
+    heuristic: impl Fn(&P::State) -> usize,
+) -> Option<Path<P::State>> {
+    let mut trail = Trail::new();
+    // (estimated total cost, cost so far, node), cheapest first
+    let mut heap = BinaryHeap::new();
+    for source in sources {
+        if trail.seen.insert(source.clone(), 0).is_none() {
+            let estimate = heuristic(&source);
+            let node = trail.push(source, None);
+            heap.push(Reverse((estimate, 0, node)));
+        }
+    }
+    while let Some(Reverse((_, cost, node))) = heap.pop() {
+        let state = trail.nodes[node].0.clone();
+        if trail.seen[&state] < cost {
+            // already got here more cheaply
+            continue;
+        }
+        if is_target(&state) {
+            return Some(trail.path(node, cost));
+        }
+        for (next, step) in space.successors(&state) {
+            let next_cost = cost + step;
+            if trail.seen.get(&next).is_none_or(|&c| next_cost < c) {
+                trail.seen.insert(next.clone(), next_cost);
+                let estimate = next_cost + heuristic(&next);
+                let next = trail.push(next, Some(node));
+                heap.push(Reverse((estimate, next_cost, next)));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a → b costs 1, b → d costs 1, a → c costs 5, c → d costs 1, and e is off on its own.
+    struct Graph;
+
+    impl Space for Graph {
+        type State = char;
+
+        fn successors(&self, state: &char) -> Vec<(char, usize)> {
+            match state {
+                'a' => vec![('c', 5), ('b', 1)],
+                'b' => vec![('d', 1)],
+                'c' => vec![('d', 1)],
+                _ => vec![],
+            }
+        }
+    }
+
+    #[test]
+    fn test_graph() {
+        let path = |states: &str, cost| Path {
+            cost,
+            states: states.chars().collect(),
+        };
+        assert_eq!(dijkstra(&Graph, ['a'], |&s| s == 'd'), Some(path("abd", 2)));
+        assert_eq!(bfs(&Graph, ['a'], |&s| s == 'c'), Some(path("ac", 1)));
+        assert_eq!(dijkstra(&Graph, ['a'], |&s| s == 'c'), Some(path("ac", 5)));
+        assert_eq!(
+            dijkstra(&Graph, ['a', 'c'], |&s| s == 'd' || s == 'b'),
+            Some(path("ab", 1))
+        );
+        assert_eq!(dijkstra(&Graph, ['c'], |&s| s == 'c'), Some(path("c", 0)));
+        assert_eq!(dijkstra(&Graph, ['a'], |&s| s == 'e'), None);
+        assert_eq!(bfs(&Graph, [], |_| true), None);
+    }
+
+    /// Open 5 × 5 grid, except for a wall down the middle with a gap at the bottom.
+    struct Maze;
+
+    impl Space for Maze {
+        type State = (isize, isize);
+
+        fn successors(&self, &(x, y): &(isize, isize)) -> Vec<((isize, isize), usize)> {
+            [(0, -1), (1, 0), (0, 1), (-1, 0)]
+                .into_iter()
+                .map(|(dx, dy)| (x + dx, y + dy))
+                .filter(|&(x, y)| (0..5).contains(&x) && (0..5).contains(&y))
+                .filter(|&(x, y)| x != 2 || y == 4)
+                .map(|s| (s, 1))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_astar() {
+        let goal = (4, 0);
+        let manhattan =
+            |&(x, y): &(isize, isize)| ((goal.0 - x).abs() + (goal.1 - y).abs()) as usize;
+        let path = astar(&Maze, [(0, 0)], |&s| s == goal, manhattan).unwrap();
+        assert_eq!(path.cost, 12);
+        assert_eq!(path.states.len(), 13);
+        assert_eq!(path.states.first(), Some(&(0, 0)));
+        assert_eq!(path.states.last(), Some(&goal));
+        assert!(path.states.contains(&(2, 4)));
+        assert_eq!(bfs(&Maze, [(0, 0)], |&s| s == goal).unwrap().cost, 12);
+    }
+}
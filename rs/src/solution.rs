@@ -0,0 +1,218 @@
+use std::str::FromStr;
+
+use crate::{error::ParseError, runner::Answer};
+
+/// The year every solution here is for.
+pub const YEAR: u16 = 2022;
+
+/// What a [`Day`] type is registered under, and what alternates go by anything but.
+pub const DEFAULT_NAME: &str = "default";
+
+/// Implement `FromStr` and [`Day`] for a `day_XX` module's input type from the module's own
+/// `get_input`, `part_1` and `part_2`, given the day and the parts' answer types. For a newtype
+/// over what `get_input` returns, whose field the parts take, it's `day!(Jets.0, 17, usize, u64)`;
+/// for a type that `get_input` returns as is, `day!(Map, 24, usize, usize)`. A day with just the
+/// one part leaves out the second type, and anything else for the `Day` impl, such as its
+/// `ALTERNATES`, goes after a `;`.
+macro_rules! day {
+    ($input:ident . 0, $($rest:tt)*) => {
+        impl std::str::FromStr for $input {
+            type Err = $crate::error::ParseError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                get_input(s.trim_end_matches('\n')).map(Self)
+            }
+        }
+
+        day!(@impl $input, [.0], $($rest)*);
+    };
+    ($input:ident, $($rest:tt)*) => {
+        impl std::str::FromStr for $input {
+            type Err = $crate::error::ParseError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                get_input(s.trim_end_matches('\n'))
+            }
+        }
+
+        day!(@impl $input, [], $($rest)*);
+    };
+    (
+        @impl $input:ident, [$($field:tt)*], $day:literal, $part1:ty, $part2:ty
+        $(; $($extra:tt)*)?
+    ) => {
+        impl $crate::solution::Day for $input {
+            const DAY: u8 = $day;
+            $($($extra)*)?
+            type Part1 = $part1;
+            type Part2 = $part2;
+
+            fn solve_part1(&self) -> $part1 {
+                part_1(&self$($field)*)
+            }
+
+            fn solve_part2(&self) -> $part2 {
+                part_2(&self$($field)*)
+            }
+        }
+    };
+    (@impl $input:ident, [$($field:tt)*], $day:literal, $part1:ty $(; $($extra:tt)*)?) => {
+        impl $crate::solution::Day for $input {
+            const DAY: u8 = $day;
+            const PARTS: u8 = 1;
+            $($($extra)*)?
+            type Part1 = $part1;
+            type Part2 = std::convert::Infallible;
+
+            fn solve_part1(&self) -> $part1 {
+                part_1(&self$($field)*)
+            }
+
+            fn solve_part2(&self) -> std::convert::Infallible {
+                unreachable!("day {} has no part 2", $day)
+            }
+        }
+    };
+}
+
+/// A day's parsed input, which knows how to solve that day's parts. Every `day_XX` module has
+/// one, so code generic over `Day` works for any of them, and each is also that day's default
+/// [`Solution`].
+///
+/// Parsing drops trailing newlines first, as cargo-aoc does, so a whole input file can be handed
+/// straight to `str::parse`.
+pub trait Day: FromStr<Err = ParseError> + 'static {
+    const DAY: u8;
+    /// How many parts there are to solve; only day 25 has just the one.
+    const PARTS: u8 = 2;
+
+    /// Other ways of solving the parts, for checking against `solve_part1` and `solve_part2`.
+    const ALTERNATES: &'static [Alternate<Self>] = &[];
+
+    type Part1: Into<Answer>;
+    /// [`Infallible`](std::convert::Infallible) for a day without a second part.
+    type Part2: Into<Answer>;
+
+    fn solve_part1(&self) -> Self::Part1;
+
+    fn solve_part2(&self) -> Self::Part2;
+}
+
+/// One way of solving a day, for registering in [`runner::SOLUTIONS`]. Every [`Day`] is one
+/// already; a day with another approach worth keeping around adds a marker type that solves the
+/// same `Input` its own way, such as [`day_17::Cycles`].
+///
+/// [`runner::SOLUTIONS`]: crate::runner::SOLUTIONS
+/// [`day_17::Cycles`]: crate::day_17::Cycles
+pub trait Solution {
+    const YEAR: u16 = YEAR;
+    const DAY: u8;
+    /// Tells alternates apart; the first one registered for a day is its default.
+    const NAME: &'static str;
+    /// As with [`Day::PARTS`], [`Solution::part2`] isn't called unless this is 2.
+    const PARTS: u8 = 2;
+    /// Other ways of solving single parts, which should all agree with `part1` and `part2`.
+    const ALTERNATES: &'static [Alternate<Self::Input>] = &[];
+
+    type Input: 'static;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part1(input: &Self::Input) -> Answer;
+
+    fn part2(input: &Self::Input) -> Answer;
+}
+
+/// Another way of solving one part of a day from the same parsed input, such as an optimization
+/// kept next to the version it's checked against. Its name needs to be unique within the day, as
+/// it's what the CLI and [`verify::cross_check`] tell implementations apart by.
+///
+/// [`verify::cross_check`]: crate::verify::cross_check
+pub struct Alternate<I: ?Sized> {
+    pub part: u8,
+    pub name: &'static str,
+    pub solve: fn(&I) -> Answer,
+}
+
+impl<D: Day> Solution for D {
+    const DAY: u8 = D::DAY;
+    const NAME: &'static str = DEFAULT_NAME;
+    const PARTS: u8 = D::PARTS;
+    const ALTERNATES: &'static [Alternate<D>] = D::ALTERNATES;
+
+    type Input = D;
+
+    fn parse(input: &str) -> Result<D, ParseError> {
+        input.parse()
+    }
+
+    fn part1(input: &D) -> Answer {
+        input.solve_part1().into()
+    }
+
+    fn part2(input: &D) -> Answer {
+        input.solve_part2().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{day_01::Inventory, day_13::Packet, day_25::FuelRequirements, runner};
+
+    fn both_parts<D: Day>(input: &str) -> (Answer, Answer) {
+        let parsed: D = input.parse().unwrap();
+        (parsed.solve_part1().into(), parsed.solve_part2().into())
+    }
+
+    #[test]
+    fn test_parse_and_solve() {
+        let input = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+        let inventory: Inventory = input.parse().unwrap();
+        assert_eq!(inventory.solve_part1(), 24000);
+        assert_eq!(inventory.solve_part2(), 45000);
+        assert_eq!(
+            both_parts::<Inventory>(input),
+            (Answer::Usize(24000), Answer::Usize(45000))
+        );
+        let err = "1000\nlots\n".parse::<Inventory>().err().unwrap();
+        assert_eq!((err.day, err.line), (Some(1), 2));
+
+        let fuel: FuelRequirements = "1=\n122\n".parse().unwrap();
+        assert_eq!(fuel.solve_part1(), "2=0");
+        assert_eq!(<FuelRequirements as Day>::PARTS, 1);
+
+        let packet: Packet = "[1,[2,3]]".parse().unwrap();
+        assert_eq!(packet.to_string(), "[1, [2, 3]]");
+    }
+
+    #[test]
+    fn test_registry() {
+        assert!(runner::days()
+            .zip(1..)
+            .all(|(d, n)| d.day == n && d.year == YEAR));
+        assert!(runner::days().all(|d| d.name == DEFAULT_NAME));
+        assert!(runner::SOLUTIONS
+            .iter()
+            .all(|s| s.iter().all(|d| d.day == s[0].day)));
+        let names: Vec<_> = runner::solutions(YEAR, 17).iter().map(|d| d.name).collect();
+        assert_eq!(names, vec![DEFAULT_NAME, "cycles"]);
+        assert_eq!(runner::solution(YEAR, 25, DEFAULT_NAME).unwrap().parts, 1);
+        assert!(runner::solutions(2021, 1).is_empty());
+        assert!(runner::solution(YEAR, 17, "brute force").is_none());
+        for solutions in runner::SOLUTIONS.iter() {
+            let mut names: Vec<_> = solutions.iter().map(|d| d.name).collect();
+            names.extend(
+                solutions
+                    .iter()
+                    .flat_map(|d| d.alternates())
+                    .map(|(_, name)| name),
+            );
+            let count = names.len();
+            names.sort();
+            names.dedup();
+            assert_eq!(names.len(), count, "day {}", solutions[0].day);
+        }
+        assert_eq!(runner::day(8).unwrap().alternates(), vec![(1, "sceneries")]);
+    }
+}
@@ -0,0 +1,176 @@
+use std::{cell::RefCell, collections::BTreeMap, error::Error, fmt, io::Write};
+
+use serde::Serialize;
+
+use crate::runner::Run;
+
+/// Enter a `tracing` span until the end of the enclosing block, taking the same arguments as
+/// `tracing::info_span!`. Without the `tracing` feature it compiles to nothing.
+macro_rules! trace_span {
+    ($($args:tt)*) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!($($args)*).entered();
+    };
+}
+
+/// Record `value` under `name` towards the current run's
+/// [`Counters`](crate::stats::Counters), e.g. 1 for each state a search pushes, or the size of
+/// its frontier at each step. Without the `tracing` feature it compiles to nothing, so `value`
+/// isn't even worked out.
+macro_rules! stat {
+    ($name:literal, $value:expr) => {
+        #[cfg(feature = "tracing")]
+        $crate::stats::record($name, $value as u64);
+    };
+}
+
+/// Everything recorded under one name during a run.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Stat {
+    /// How many times it was recorded.
+    pub samples: u64,
+    pub total: u64,
+    pub max: u64,
+}
+
+impl fmt::Display for Stat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.total == self.samples && self.max <= 1 {
+            write!(f, "{}", self.total)
+        } else {
+            write!(
+                f,
+                "{} samples, total {}, max {}",
+                self.samples, self.total, self.max
+            )
+        }
+    }
+}
+
+/// What a solver recorded with `stat!` during one run, by name. Always empty without the
+/// `tracing` feature.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct Counters(pub BTreeMap<&'static str, Stat>);
+
+impl Counters {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Stat> {
+        self.0.get(name)
+    }
+}
+
+impl fmt::Display for Counters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (name, stat)) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{}: {}", name, stat)?;
+        }
+        Ok(())
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Counters> = RefCell::default();
+}
+
+/// What `stat!` does with the feature on.
+pub fn record(name: &'static str, value: u64) {
+    CURRENT.with(|stats| {
+        let mut stats = stats.borrow_mut();
+        let stat = stats.0.entry(name).or_default();
+        stat.samples += 1;
+        stat.total += value;
+        stat.max = stat.max.max(value);
+    });
+}
+
+/// Everything recorded on this thread since the last call.
+pub fn take() -> Counters {
+    CURRENT.with(|stats| stats.take())
+}
+
+/// Writes each run's stats as a line of JSON, e.g. to see whether a slow input made a search
+/// explode or just got past its pruning.
+pub struct Exporter<W: Write> {
+    out: W,
+}
+
+#[derive(Serialize)]
+struct Line<'a> {
+    day: u8,
+    part: u8,
+    stats: &'a Counters,
+}
+
+impl<W: Write> Exporter<W> {
+    pub fn new(out: W) -> Self {
+        Self { out }
+    }
+
+    pub fn export(&mut self, run: &Run) -> Result<(), Box<dyn Error>> {
+        let line = Line {
+            day: run.day,
+            part: run.part,
+            stats: &run.stats,
+        };
+        serde_json::to_writer(&mut self.out, &line)?;
+        writeln!(self.out)?;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<(), Box<dyn Error>> {
+        Ok(self.out.flush()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner;
+
+    #[test]
+    fn test_record() {
+        take();
+        record("pushed", 1);
+        record("pushed", 1);
+        record("frontier", 3);
+        record("frontier", 7);
+        let stats = take();
+        assert_eq!(
+            stats.get("frontier"),
+            Some(&Stat {
+                samples: 2,
+                total: 10,
+                max: 7
+            })
+        );
+        assert_eq!(
+            stats.to_string(),
+            "frontier: 2 samples, total 10, max 7; pushed: 2"
+        );
+        assert!(take().is_empty());
+    }
+
+    #[test]
+    fn test_export() {
+        let input = include_str!("../test_data/day_16.txt");
+        let runs = runner::day(16).unwrap().run(input, Some(1)).unwrap();
+        let mut out = Vec::new();
+        let mut exporter = Exporter::new(&mut out);
+        exporter.export(&runs[0]).unwrap();
+        let line: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!((&line["day"], &line["part"]), (&16.into(), &1.into()));
+        let stack = &line["stats"]["day_16.release.stack"];
+        if cfg!(feature = "tracing") {
+            assert!(stack["max"].as_u64().unwrap() > 1);
+        } else {
+            assert!(stack.is_null());
+        }
+    }
+}
@@ -0,0 +1,317 @@
+use std::{collections::BTreeMap, error::Error, fmt, io::Read, path::Path};
+
+use serde::Deserialize;
+
+use crate::{
+    error::ParseError,
+    runner::{Answer, Day},
+};
+
+/// Where the checked-in answers live, relative to the crate root.
+pub const DEFAULT_ANSWERS_PATH: &str = "answers.csv";
+
+/// Known answers to the real inputs, keyed by day and part.
+///
+/// The file is CSV with the columns `day,part,answer,answer_type`, where `answer_type` is one of
+/// the names [`Answer::type_name`] gives, so `report --format csv` output can be trimmed down into
+/// one.
+#[derive(Clone, Debug, Default)]
+pub struct Registry {
+    answers: BTreeMap<(u8, u8), Answer>,
+}
+
+#[derive(Deserialize)]
+struct Row {
+    day: u8,
+    part: u8,
+    answer: String,
+    answer_type: String,
+}
+
+impl Registry {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn Error>> {
+        let path = path.as_ref();
+        let file = std::fs::File::open(path)
+            .map_err(|e| format!("couldn't read {}: {}", path.display(), e))?;
+        Self::from_reader(file)
+    }
+
+    pub fn from_reader(reader: impl Read) -> Result<Self, Box<dyn Error>> {
+        let mut answers = BTreeMap::new();
+        for row in csv::Reader::from_reader(reader).deserialize() {
+            let row: Row = row?;
+            let answer = Answer::from_typed(&row.answer_type, &row.answer)?;
+            if answers.insert((row.day, row.part), answer).is_some() {
+                Err(format!("day {} part {} is listed twice", row.day, row.part))?;
+            }
+        }
+        Ok(Self { answers })
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&Answer> {
+        self.answers.get(&(day, part))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    Match,
+    Mismatch {
+        expected: Answer,
+        actual: Answer,
+    },
+    /// The registry has nothing for this part; holds what the solver came up with.
+    Unknown(Answer),
+    /// The generator rejected the input.
+    Failed(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Verification {
+    pub day: u8,
+    /// Which alternate solution this was, if it wasn't the day's default.
+    pub implementation: Option<&'static str>,
+    pub part: Option<u8>,
+    pub status: Status,
+}
+
+impl Verification {
+    pub fn passed(&self) -> bool {
+        matches!(self.status, Status::Match | Status::Unknown(_))
+    }
+}
+
+impl fmt::Display for Verification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Day {}", self.day)?;
+        if let Some(name) = self.implementation {
+            write!(f, " ({})", name)?;
+        }
+        if let Some(part) = self.part {
+            write!(f, " - Part {}", part)?;
+        }
+        match &self.status {
+            Status::Match => write!(f, ": ok"),
+            Status::Mismatch { expected, actual } => {
+                write!(f, ": MISMATCH, expected {} but got {}", expected, actual)
+            }
+            Status::Unknown(actual) => write!(f, ": no known answer (got {})", actual),
+            Status::Failed(e) => write!(f, ": FAILED, {}", e),
+        }
+    }
+}
+
+fn status(expected: Option<&Answer>, actual: Answer) -> Status {
+    match expected {
+        Some(expected) if *expected == actual => Status::Match,
+        Some(expected) => Status::Mismatch {
+            expected: expected.clone(),
+            actual,
+        },
+        None => Status::Unknown(actual),
+    }
+}
+
+/// Solve every part of `day`, with its alternates as well, and compare the answers against the
+/// registry.
+pub fn verify(day: &Day, input: &str, registry: &Registry) -> Vec<Verification> {
+    let mut verifications = Vec::new();
+    for part in 1..=day.parts {
+        match day.solve_all(input, part) {
+            Ok(answers) => {
+                verifications.extend(answers.into_iter().map(|(name, answer)| Verification {
+                    day: day.day,
+                    implementation: name.or(day.alternate()),
+                    part: Some(part),
+                    status: status(registry.get(day.day, part), answer),
+                }))
+            }
+            Err(e) => {
+                return vec![Verification {
+                    day: day.day,
+                    implementation: day.alternate(),
+                    part: None,
+                    status: Status::Failed(e.to_string()),
+                }]
+            }
+        }
+    }
+    verifications
+}
+
+/// What every implementation of one part came up with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CrossCheck {
+    pub day: u8,
+    pub part: u8,
+    /// Each implementation's name and answer, starting with the day's default.
+    pub answers: Vec<(&'static str, Answer)>,
+}
+
+impl CrossCheck {
+    pub fn agreed(&self) -> bool {
+        self.answers.windows(2).all(|w| w[0].1 == w[1].1)
+    }
+}
+
+impl fmt::Display for CrossCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Day {} - Part {}: ", self.day, self.part)?;
+        match &self.answers[..] {
+            [] => write!(f, "no answers"),
+            [(name, answer)] => write!(f, "only {}, which got {}", name, answer),
+            [(_, answer), ..] if self.agreed() => {
+                let names: Vec<&str> = self.answers.iter().map(|(name, _)| *name).collect();
+                write!(f, "{} agree on {}", names.join(", "), answer)
+            }
+            _ => {
+                write!(f, "DISAGREEMENT")?;
+                for (name, answer) in self.answers.iter() {
+                    write!(f, ", {} got {}", name, answer)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Solve each part with every implementation there is of it, whole [`Solution`]s and single-part
+/// [`Alternate`]s alike, to see whether they agree. `solutions` are a day's, as from
+/// [`runner::solutions`]. No answers are needed, so this works just as well on generated
+/// inputs.
+///
+/// [`Solution`]: crate::solution::Solution
+/// [`Alternate`]: crate::solution::Alternate
+/// [`runner::solutions`]: crate::runner::solutions
+pub fn cross_check(solutions: &[Day], input: &str) -> Result<Vec<CrossCheck>, ParseError> {
+    let Some(first) = solutions.first() else {
+        return Ok(Vec::new());
+    };
+    let mut checks = Vec::new();
+    for part in 1..=first.parts {
+        let mut answers = Vec::new();
+        for solution in solutions {
+            for (name, answer) in solution.solve_all(input, part)? {
+                answers.push((name.unwrap_or(solution.name), answer));
+            }
+        }
+        checks.push(CrossCheck {
+            day: first.day,
+            part,
+            answers,
+        });
+    }
+    Ok(checks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{inputgen, runner, solution::YEAR};
+
+    const REGISTRY: &str = "day,part,answer,answer_type\n\
+        5,1,CMZ,String\n\
+        5,2,MCM,String\n\
+        21,1,152,i64\n";
+
+    #[test]
+    fn test_registry() {
+        let registry = Registry::from_reader(REGISTRY.as_bytes()).unwrap();
+        assert_eq!(registry.get(5, 1), Some(&Answer::String("CMZ".to_owned())));
+        assert_eq!(registry.get(21, 1), Some(&Answer::I64(152)));
+        assert_eq!(registry.get(21, 2), None);
+        let duplicated = format!("{}21,1,152,i64\n", REGISTRY);
+        assert!(Registry::from_reader(duplicated.as_bytes()).is_err());
+        let mistyped = "day,part,answer,answer_type\n1,1,abc,usize\n";
+        assert!(Registry::from_reader(mistyped.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_verify() {
+        let registry = Registry::from_reader(REGISTRY.as_bytes()).unwrap();
+        let day_5 = runner::day(5).unwrap();
+        let statuses: Vec<Status> =
+            verify(day_5, include_str!("../test_data/day_05.txt"), &registry)
+                .into_iter()
+                .map(|v| v.status)
+                .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                Status::Match,
+                Status::Mismatch {
+                    expected: Answer::String("MCM".to_owned()),
+                    actual: Answer::String("MCD".to_owned()),
+                },
+            ]
+        );
+        let failed = verify(day_5, "nonsense", &registry);
+        assert_eq!(failed.len(), 1);
+        assert!(!failed[0].passed());
+
+        let registry =
+            Registry::from_reader("day,part,answer,answer_type\n17,1,3068,usize\n".as_bytes())
+                .unwrap();
+        let cycles = runner::solution(YEAR, 17, "cycles").unwrap();
+        let verified = verify(
+            cycles,
+            ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>",
+            &registry,
+        );
+        assert_eq!(verified[0].status, Status::Match);
+        assert_eq!(verified[0].to_string(), "Day 17 (cycles) - Part 1: ok");
+    }
+
+    #[test]
+    fn test_cross_check() {
+        let checks = cross_check(
+            runner::solutions(YEAR, 8),
+            "30373\n25512\n65332\n33549\n35390\n",
+        )
+        .unwrap();
+        assert_eq!(
+            checks[0].answers,
+            vec![
+                ("default", Answer::Usize(21)),
+                ("sceneries", Answer::Usize(21))
+            ]
+        );
+        assert_eq!(
+            checks[0].to_string(),
+            "Day 8 - Part 1: default, sceneries agree on 21"
+        );
+        assert_eq!(
+            checks[1].to_string(),
+            "Day 8 - Part 2: only default, which got 8"
+        );
+        let disagreement = CrossCheck {
+            answers: vec![
+                ("default", Answer::Usize(21)),
+                ("sceneries", Answer::Usize(20)),
+            ],
+            ..checks[0].clone()
+        };
+        assert!(!disagreement.agreed());
+        assert_eq!(
+            disagreement.to_string(),
+            "Day 8 - Part 1: DISAGREEMENT, default got 21, sceneries got 20"
+        );
+        assert!(cross_check(runner::solutions(YEAR, 8), "3x\n").is_err());
+    }
+
+    #[test]
+    fn test_alternates_agree() {
+        let alternated = runner::SOLUTIONS
+            .iter()
+            .filter(|s| s.len() > 1 || !s[0].alternates().is_empty());
+        for solutions in alternated {
+            let generator = inputgen::generator(solutions[0].day).unwrap();
+            for seed in 0..5 {
+                let input = generator.generate(seed, 10);
+                for check in cross_check(solutions, &input).unwrap() {
+                    assert!(check.agreed(), "seed {}: {}", seed, check);
+                }
+            }
+        }
+    }
+}
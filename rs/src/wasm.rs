@@ -0,0 +1,102 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    inputgen,
+    record::{self, Frames},
+    runner,
+    solution::YEAR,
+};
+
+/// Hundredths of a second between GIF frames, as with the `animate` command.
+const GIF_DELAY: u16 = 5;
+
+fn day(n: u8) -> Result<&'static runner::Day, JsError> {
+    runner::day(n).ok_or_else(|| JsError::new(&format!("no such day: {}", n)))
+}
+
+/// Solve one part of a day, with its answer as a string. Day 10's part 2 comes back as the
+/// letters drawn out in `█` and spaces, as it does on the command line.
+#[wasm_bindgen]
+pub fn solve(day: u8, part: u8, input: &str) -> Result<String, JsError> {
+    solve_with(self::day(day)?, part, input)
+}
+
+/// As with `solve`, but using the day's solution called `name`, one of its `implementations`.
+#[wasm_bindgen]
+pub fn solve_impl(day: u8, name: &str, part: u8, input: &str) -> Result<String, JsError> {
+    let solution = runner::solution(YEAR, day, name).ok_or_else(|| {
+        JsError::new(&format!("no implementation of day {} called {}", day, name))
+    })?;
+    solve_with(solution, part, input)
+}
+
+fn solve_with(solution: &runner::Day, part: u8, input: &str) -> Result<String, JsError> {
+    let answer = solution
+        .generate(input)
+        .map_err(|e| JsError::new(&e.to_string()))?
+        .solve(part)
+        .ok_or_else(|| JsError::new(&format!("no such part: {}", part)))?;
+    Ok(answer.to_string())
+}
+
+/// How many parts a day has.
+#[wasm_bindgen]
+pub fn parts(day: u8) -> Result<u8, JsError> {
+    Ok(self::day(day)?.parts)
+}
+
+/// The names of a day's solutions, default first.
+#[wasm_bindgen]
+pub fn implementations(day: u8) -> Vec<String> {
+    runner::solutions(YEAR, day)
+        .iter()
+        .map(|d| d.name.to_owned())
+        .collect()
+}
+
+/// The days that `animate_text` and `animate_gif` work for.
+#[wasm_bindgen]
+pub fn animated_days() -> Vec<u8> {
+    record::ANIMATIONS.iter().map(|a| a.day).collect()
+}
+
+fn frames(day: u8, input: &str, every: usize, limit: usize) -> Result<Frames, JsError> {
+    let animation = record::animation(day)
+        .ok_or_else(|| JsError::new(&format!("no animation for day {}", day)))?;
+    let mut frames = Frames::every(every).limit(limit);
+    animation
+        .run(input, &mut frames)
+        .map_err(|e| JsError::new(&e.to_string()))?;
+    Ok(frames)
+}
+
+/// Every `every`th frame of a day's simulation, up to `limit` of them, as text under
+/// `-- frame N --` headers.
+#[wasm_bindgen]
+pub fn animate_text(day: u8, input: &str, every: usize, limit: usize) -> Result<String, JsError> {
+    let mut out = Vec::new();
+    frames(day, input, every, limit)?.write_text(&mut out)?;
+    Ok(String::from_utf8(out)?)
+}
+
+/// The same frames as a looping GIF, for showing through a blob URL.
+#[wasm_bindgen]
+pub fn animate_gif(
+    day: u8,
+    input: &str,
+    every: usize,
+    limit: usize,
+    scale: usize,
+) -> Result<Vec<u8>, JsError> {
+    let mut out = Vec::new();
+    frames(day, input, every, limit)?.write_gif(&mut out, scale.max(1), GIF_DELAY)?;
+    Ok(out)
+}
+
+/// A random input for the day, as from the `generate` command.
+#[wasm_bindgen]
+pub fn generate_input(day: u8, seed: u64, size: usize) -> Result<String, JsError> {
+    inputgen::generator(day)
+        .map(|g| g.generate(seed, size))
+        .ok_or_else(|| JsError::new(&format!("no such day: {}", day)))
+}